@@ -9,37 +9,505 @@ import 'package:flutter_rust_bridge/flutter_rust_bridge.dart';
 import 'package:uuid/uuid.dart';
 
 abstract class RustCore {
-  Future<void> loadRom(
-      {required Uint8List romData, Uint8List? ramData, dynamic hint});
+  Future<int> createEmulator(
+      {required Uint8List romData,
+      Uint8List? ramData,
+      Uint8List? bootRom,
+      required int model,
+      dynamic hint});
 
-  FlutterRustBridgeTaskConstMeta get kLoadRomConstMeta;
+  FlutterRustBridgeTaskConstMeta get kCreateEmulatorConstMeta;
 
-  Future<Uint8List?> unloadEmulator({dynamic hint});
+  Future<Uint8List?> destroyEmulator({required int id, dynamic hint});
 
-  FlutterRustBridgeTaskConstMeta get kUnloadEmulatorConstMeta;
+  FlutterRustBridgeTaskConstMeta get kDestroyEmulatorConstMeta;
 
-  Future<Uint32List?> renderFrame({dynamic hint});
+  Future<FrameOutput> renderFrame({required int id, dynamic hint});
 
   FlutterRustBridgeTaskConstMeta get kRenderFrameConstMeta;
 
-  Future<void> setButtonsState({required Uint8List buttonStates, dynamic hint});
+  Future<void> setButtonsState(
+      {required int id, required Uint8List buttonStates, dynamic hint});
 
   FlutterRustBridgeTaskConstMeta get kSetButtonsStateConstMeta;
 
-  Future<void> load(
-      {required Uint8List romData, Uint8List? ramData, dynamic hint});
+  Future<void> setAudioSampleRate(
+      {required int id, required int sampleRate, dynamic hint});
 
-  FlutterRustBridgeTaskConstMeta get kLoadConstMeta;
+  FlutterRustBridgeTaskConstMeta get kSetAudioSampleRateConstMeta;
 
-  Future<Uint8List?> unload({dynamic hint});
+  Future<Int16List?> drainAudioSamples({required int id, dynamic hint});
 
-  FlutterRustBridgeTaskConstMeta get kUnloadConstMeta;
+  FlutterRustBridgeTaskConstMeta get kDrainAudioSamplesConstMeta;
 
-  Future<Uint32List?> render({dynamic hint});
+  Future<void> setAudioFilterModel(
+      {required int id, required bool cgb, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSetAudioFilterModelConstMeta;
+
+  Future<void> setAudioChannelMuted(
+      {required int id,
+      required int channel,
+      required bool muted,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSetAudioChannelMutedConstMeta;
+
+  Future<void> setAudioChannelSoloed(
+      {required int id,
+      required int channel,
+      required bool soloed,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSetAudioChannelSoloedConstMeta;
+
+  Future<List<ChannelState>?> getAudioChannelStates(
+      {required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kGetAudioChannelStatesConstMeta;
+
+  Future<void> startAudioRecording({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStartAudioRecordingConstMeta;
+
+  Future<Uint8List?> stopAudioRecording({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStopAudioRecordingConstMeta;
+
+  Future<AudioSyncOutput?> runUntilAudioSamples(
+      {required int id,
+      required int sampleFrames,
+      required double bufferFill,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kRunUntilAudioSamplesConstMeta;
+
+  Future<Uint8List?> saveEmulatorState({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSaveEmulatorStateConstMeta;
+
+  Future<void> loadEmulatorState(
+      {required int id, required Uint8List state, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kLoadEmulatorStateConstMeta;
+
+  Future<void> setRewindConfig(
+      {required int id,
+      required int memoryBudget,
+      required int framesPerSnapshot,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSetRewindConfigConstMeta;
+
+  Future<int> rewindEmulator(
+      {required int id, required int frames, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kRewindEmulatorConstMeta;
+
+  Future<void> setEmulationSpeed(
+      {required int id, required double speed, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSetEmulationSpeedConstMeta;
+
+  Future<MachineState?> stepEmulatorInstruction(
+      {required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStepEmulatorInstructionConstMeta;
+
+  Future<MachineState?> stepEmulatorScanline({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStepEmulatorScanlineConstMeta;
+
+  Future<MachineState?> stepEmulatorFrame({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStepEmulatorFrameConstMeta;
+
+  Future<Uint32List?> getScreenBuffer({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kGetScreenBufferConstMeta;
+
+  Future<bool> addEmulatorBreakpoint(
+      {required int id,
+      required int address,
+      int? bank,
+      String? condition,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kAddEmulatorBreakpointConstMeta;
+
+  Future<void> removeEmulatorBreakpoint(
+      {required int id, required int address, int? bank, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kRemoveEmulatorBreakpointConstMeta;
+
+  Future<void> addEmulatorWatchpoint(
+      {required int id,
+      required int start,
+      required int end,
+      required bool read,
+      required bool write,
+      required bool execute,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kAddEmulatorWatchpointConstMeta;
+
+  Future<void> removeEmulatorWatchpoint(
+      {required int id, required int start, required int end, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kRemoveEmulatorWatchpointConstMeta;
+
+  Future<void> clearEmulatorBreakpoints({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kClearEmulatorBreakpointsConstMeta;
+
+  Future<void> setEmulatorBreakOnInterrupt(
+      {required int id, required bool enabled, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSetEmulatorBreakOnInterruptConstMeta;
+
+  Future<StopReason?> getStopReason({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kGetStopReasonConstMeta;
+
+  Future<void> resumeEmulator({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kResumeEmulatorConstMeta;
+
+  Future<List<Instruction>?> disassembleMemory(
+      {required int id,
+      required int address,
+      required int count,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kDisassembleMemoryConstMeta;
+
+  Future<bool> startEmulatorTraceFile(
+      {required int id, required String path, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStartEmulatorTraceFileConstMeta;
+
+  Future<void> startEmulatorTraceBuffer(
+      {required int id, required int capacity, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStartEmulatorTraceBufferConstMeta;
+
+  Future<void> stopEmulatorTrace({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStopEmulatorTraceConstMeta;
+
+  Future<String?> getEmulatorTrace({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kGetEmulatorTraceConstMeta;
+
+  Future<bool> startGdbServer(
+      {required int id, required int port, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStartGdbServerConstMeta;
+
+  Future<void> stopGdbServer({dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStopGdbServerConstMeta;
+
+  Future<int> create(
+      {required Uint8List romData,
+      Uint8List? ramData,
+      Uint8List? bootRom,
+      required int model,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kCreateConstMeta;
+
+  Future<Uint8List?> destroy({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kDestroyConstMeta;
+
+  Future<FrameOutput> render({required int id, dynamic hint});
 
   FlutterRustBridgeTaskConstMeta get kRenderConstMeta;
 
-  Future<void> setButtons({required Uint8List buttonStates, dynamic hint});
+  Future<void> setButtons(
+      {required int id, required Uint8List buttonStates, dynamic hint});
 
   FlutterRustBridgeTaskConstMeta get kSetButtonsConstMeta;
+
+  Future<void> setSampleRate(
+      {required int id, required int sampleRate, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSetSampleRateConstMeta;
+
+  Future<Int16List?> drainAudio({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kDrainAudioConstMeta;
+
+  Future<void> setCgbAudioFilter(
+      {required int id, required bool enabled, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSetCgbAudioFilterConstMeta;
+
+  Future<void> muteChannel(
+      {required int id,
+      required int channel,
+      required bool muted,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kMuteChannelConstMeta;
+
+  Future<void> soloChannel(
+      {required int id,
+      required int channel,
+      required bool soloed,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSoloChannelConstMeta;
+
+  Future<List<ChannelState>?> channelStates({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kChannelStatesConstMeta;
+
+  Future<void> startRecording({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStartRecordingConstMeta;
+
+  Future<Uint8List?> stopRecording({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStopRecordingConstMeta;
+
+  Future<AudioSyncOutput?> renderAudio(
+      {required int id,
+      required int sampleFrames,
+      required double bufferFill,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kRenderAudioConstMeta;
+
+  Future<Uint8List?> saveState({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSaveStateConstMeta;
+
+  Future<void> loadState(
+      {required int id, required Uint8List state, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kLoadStateConstMeta;
+
+  Future<void> configureRewind(
+      {required int id,
+      required int memoryBudget,
+      required int framesPerSnapshot,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kConfigureRewindConstMeta;
+
+  Future<int> rewind({required int id, required int frames, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kRewindConstMeta;
+
+  Future<void> setSpeed(
+      {required int id, required double multiplier, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kSetSpeedConstMeta;
+
+  Future<MachineState?> stepInstruction({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStepInstructionConstMeta;
+
+  Future<MachineState?> stepScanline({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStepScanlineConstMeta;
+
+  Future<MachineState?> stepFrame({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStepFrameConstMeta;
+
+  Future<Uint32List?> currentFrame({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kCurrentFrameConstMeta;
+
+  Future<bool> addBreakpoint(
+      {required int id,
+      required int address,
+      int? bank,
+      String? condition,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kAddBreakpointConstMeta;
+
+  Future<void> removeBreakpoint(
+      {required int id, required int address, int? bank, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kRemoveBreakpointConstMeta;
+
+  Future<void> addWatchpoint(
+      {required int id,
+      required int start,
+      required int end,
+      required bool read,
+      required bool write,
+      required bool execute,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kAddWatchpointConstMeta;
+
+  Future<void> removeWatchpoint(
+      {required int id, required int start, required int end, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kRemoveWatchpointConstMeta;
+
+  Future<void> clearBreakpoints({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kClearBreakpointsConstMeta;
+
+  Future<void> breakOnInterrupt(
+      {required int id, required bool enabled, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kBreakOnInterruptConstMeta;
+
+  Future<StopReason?> stopReason({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStopReasonConstMeta;
+
+  Future<void> resume({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kResumeConstMeta;
+
+  Future<List<Instruction>?> disassemble(
+      {required int id,
+      required int address,
+      required int count,
+      dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kDisassembleConstMeta;
+
+  Future<bool> traceToFile(
+      {required int id, required String path, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kTraceToFileConstMeta;
+
+  Future<void> traceToBuffer(
+      {required int id, required int lines, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kTraceToBufferConstMeta;
+
+  Future<void> stopTrace({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStopTraceConstMeta;
+
+  Future<String?> traceLog({required int id, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kTraceLogConstMeta;
+
+  Future<bool> startGdb({required int id, required int port, dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStartGdbConstMeta;
+
+  Future<void> stopGdb({dynamic hint});
+
+  FlutterRustBridgeTaskConstMeta get kStopGdbConstMeta;
+}
+
+class ChannelState {
+  final bool enabled;
+  final double frequencyHz;
+  final int volume;
+  final int duty;
+  final int lengthRemaining;
+
+  const ChannelState({
+    required this.enabled,
+    required this.frequencyHz,
+    required this.volume,
+    required this.duty,
+    required this.lengthRemaining,
+  });
+}
+
+class AudioSyncOutput {
+  final Uint32List frame;
+  final Int16List samples;
+  final StopReason? stopReason;
+
+  const AudioSyncOutput({
+    required this.frame,
+    required this.samples,
+    this.stopReason,
+  });
+}
+
+class MachineState {
+  final int a;
+  final int b;
+  final int c;
+  final int d;
+  final int e;
+  final int f;
+  final int h;
+  final int l;
+  final int pc;
+  final int sp;
+  final bool ime;
+  final bool halted;
+  final int ly;
+  final int ppuMode;
+
+  const MachineState({
+    required this.a,
+    required this.b,
+    required this.c,
+    required this.d,
+    required this.e,
+    required this.f,
+    required this.h,
+    required this.l,
+    required this.pc,
+    required this.sp,
+    required this.ime,
+    required this.halted,
+    required this.ly,
+    required this.ppuMode,
+  });
+}
+
+class StopReason {
+  final StopKind kind;
+  final int address;
+
+  const StopReason({
+    required this.kind,
+    required this.address,
+  });
+}
+
+enum StopKind {
+  breakpoint,
+  readWatchpoint,
+  writeWatchpoint,
+  executeWatchpoint,
+  interrupt,
+}
+
+class Instruction {
+  final int address;
+  final Uint8List bytes;
+  final String text;
+  final int length;
+  final int cycles;
+  final int cyclesTaken;
+
+  const Instruction({
+    required this.address,
+    required this.bytes,
+    required this.text,
+    required this.length,
+    required this.cycles,
+    required this.cyclesTaken,
+  });
+}
+
+class FrameOutput {
+  final Uint32List frame;
+  final StopReason? stopReason;
+
+  const FrameOutput({
+    required this.frame,
+    this.stopReason,
+  });
 }
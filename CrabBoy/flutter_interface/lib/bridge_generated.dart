@@ -26,50 +26,59 @@ class RustCoreImpl implements RustCore {
   factory RustCoreImpl.wasm(FutureOr<WasmModule> module) =>
       RustCoreImpl(module as ExternalLibrary);
   RustCoreImpl.raw(this._platform);
-  Future<void> loadRom(
-      {required Uint8List romData, Uint8List? ramData, dynamic hint}) {
+  Future<int> createEmulator(
+      {required Uint8List romData,
+      Uint8List? ramData,
+      Uint8List? bootRom,
+      required int model,
+      dynamic hint}) {
     var arg0 = _platform.api2wire_uint_8_list(romData);
     var arg1 = _platform.api2wire_opt_uint_8_list(ramData);
+    var arg2 = _platform.api2wire_opt_uint_8_list(bootRom);
+    var arg3 = api2wire_u8(model);
     return _platform.executeNormal(FlutterRustBridgeTask(
-      callFfi: (port_) => _platform.inner.wire_load_rom(port_, arg0, arg1),
-      parseSuccessData: _wire2api_unit,
-      parseErrorData: _wire2api_error,
-      constMeta: kLoadRomConstMeta,
-      argValues: [romData, ramData],
+      callFfi: (port_) =>
+          _platform.inner.wire_create_emulator(port_, arg0, arg1, arg2, arg3),
+      parseSuccessData: _wire2api_u32,
+      parseErrorData: _wire2api_FrbAnyhowException,
+      constMeta: kCreateEmulatorConstMeta,
+      argValues: [romData, ramData, bootRom, model],
       hint: hint,
     ));
   }
 
-  FlutterRustBridgeTaskConstMeta get kLoadRomConstMeta =>
+  FlutterRustBridgeTaskConstMeta get kCreateEmulatorConstMeta =>
       const FlutterRustBridgeTaskConstMeta(
-        debugName: "load_rom",
-        argNames: ["romData", "ramData"],
+        debugName: "create_emulator",
+        argNames: ["romData", "ramData", "bootRom", "model"],
       );
 
-  Future<Uint8List?> unloadEmulator({dynamic hint}) {
+  Future<Uint8List?> destroyEmulator({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
     return _platform.executeNormal(FlutterRustBridgeTask(
-      callFfi: (port_) => _platform.inner.wire_unload_emulator(port_),
+      callFfi: (port_) => _platform.inner.wire_destroy_emulator(port_, arg0),
       parseSuccessData: _wire2api_opt_uint_8_list,
       parseErrorData: _wire2api_error,
-      constMeta: kUnloadEmulatorConstMeta,
-      argValues: [],
+      constMeta: kDestroyEmulatorConstMeta,
+      argValues: [id],
       hint: hint,
     ));
   }
 
-  FlutterRustBridgeTaskConstMeta get kUnloadEmulatorConstMeta =>
+  FlutterRustBridgeTaskConstMeta get kDestroyEmulatorConstMeta =>
       const FlutterRustBridgeTaskConstMeta(
-        debugName: "unload_emulator",
-        argNames: [],
+        debugName: "destroy_emulator",
+        argNames: ["id"],
       );
 
-  Future<Uint32List?> renderFrame({dynamic hint}) {
+  Future<FrameOutput> renderFrame({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
     return _platform.executeNormal(FlutterRustBridgeTask(
-      callFfi: (port_) => _platform.inner.wire_render_frame(port_),
-      parseSuccessData: _wire2api_opt_uint_32_list,
-      parseErrorData: _wire2api_error,
+      callFfi: (port_) => _platform.inner.wire_render_frame(port_, arg0),
+      parseSuccessData: _wire2api_frame_output,
+      parseErrorData: _wire2api_FrbAnyhowException,
       constMeta: kRenderFrameConstMeta,
-      argValues: [],
+      argValues: [id],
       hint: hint,
     ));
   }
@@ -77,18 +86,20 @@ class RustCoreImpl implements RustCore {
   FlutterRustBridgeTaskConstMeta get kRenderFrameConstMeta =>
       const FlutterRustBridgeTaskConstMeta(
         debugName: "render_frame",
-        argNames: [],
+        argNames: ["id"],
       );
 
   Future<void> setButtonsState(
-      {required Uint8List buttonStates, dynamic hint}) {
-    var arg0 = _platform.api2wire_uint_8_list(buttonStates);
+      {required int id, required Uint8List buttonStates, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = _platform.api2wire_uint_8_list(buttonStates);
     return _platform.executeNormal(FlutterRustBridgeTask(
-      callFfi: (port_) => _platform.inner.wire_set_buttons_state(port_, arg0),
+      callFfi: (port_) =>
+          _platform.inner.wire_set_buttons_state(port_, arg0, arg1),
       parseSuccessData: _wire2api_unit,
       parseErrorData: _wire2api_error,
       constMeta: kSetButtonsStateConstMeta,
-      argValues: [buttonStates],
+      argValues: [id, buttonStates],
       hint: hint,
     ));
   }
@@ -96,370 +107,3153 @@ class RustCoreImpl implements RustCore {
   FlutterRustBridgeTaskConstMeta get kSetButtonsStateConstMeta =>
       const FlutterRustBridgeTaskConstMeta(
         debugName: "set_buttons_state",
-        argNames: ["buttonStates"],
+        argNames: ["id", "buttonStates"],
       );
 
-  Future<void> load(
-      {required Uint8List romData, Uint8List? ramData, dynamic hint}) {
-    var arg0 = _platform.api2wire_uint_8_list(romData);
-    var arg1 = _platform.api2wire_opt_uint_8_list(ramData);
+  Future<void> setAudioSampleRate(
+      {required int id, required int sampleRate, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u32(sampleRate);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_set_audio_sample_rate(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kSetAudioSampleRateConstMeta,
+      argValues: [id, sampleRate],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSetAudioSampleRateConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "set_audio_sample_rate",
+        argNames: ["id", "sampleRate"],
+      );
+
+  Future<Int16List?> drainAudioSamples({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_drain_audio_samples(port_, arg0),
+      parseSuccessData: _wire2api_opt_int_16_list,
+      parseErrorData: _wire2api_error,
+      constMeta: kDrainAudioSamplesConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kDrainAudioSamplesConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "drain_audio_samples",
+        argNames: ["id"],
+      );
+
+  Future<void> setAudioFilterModel(
+      {required int id, required bool cgb, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_bool(cgb);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_set_audio_filter_model(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kSetAudioFilterModelConstMeta,
+      argValues: [id, cgb],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSetAudioFilterModelConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "set_audio_filter_model",
+        argNames: ["id", "cgb"],
+      );
+
+  Future<void> setAudioChannelMuted(
+      {required int id,
+      required int channel,
+      required bool muted,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u8(channel);
+    var arg2 = api2wire_bool(muted);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_set_audio_channel_muted(port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kSetAudioChannelMutedConstMeta,
+      argValues: [id, channel, muted],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSetAudioChannelMutedConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "set_audio_channel_muted",
+        argNames: ["id", "channel", "muted"],
+      );
+
+  Future<void> setAudioChannelSoloed(
+      {required int id,
+      required int channel,
+      required bool soloed,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u8(channel);
+    var arg2 = api2wire_bool(soloed);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_set_audio_channel_soloed(
+          port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kSetAudioChannelSoloedConstMeta,
+      argValues: [id, channel, soloed],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSetAudioChannelSoloedConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "set_audio_channel_soloed",
+        argNames: ["id", "channel", "soloed"],
+      );
+
+  Future<List<ChannelState>?> getAudioChannelStates(
+      {required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_get_audio_channel_states(port_, arg0),
+      parseSuccessData: _wire2api_opt_list_channel_state,
+      parseErrorData: _wire2api_error,
+      constMeta: kGetAudioChannelStatesConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kGetAudioChannelStatesConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "get_audio_channel_states",
+        argNames: ["id"],
+      );
+
+  Future<void> startAudioRecording({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_start_audio_recording(port_, arg0),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kStartAudioRecordingConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStartAudioRecordingConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "start_audio_recording",
+        argNames: ["id"],
+      );
+
+  Future<Uint8List?> stopAudioRecording({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_stop_audio_recording(port_, arg0),
+      parseSuccessData: _wire2api_opt_uint_8_list,
+      parseErrorData: _wire2api_error,
+      constMeta: kStopAudioRecordingConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStopAudioRecordingConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "stop_audio_recording",
+        argNames: ["id"],
+      );
+
+  Future<AudioSyncOutput?> runUntilAudioSamples(
+      {required int id,
+      required int sampleFrames,
+      required double bufferFill,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u32(sampleFrames);
+    var arg2 = api2wire_f32(bufferFill);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_run_until_audio_samples(port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_opt_audio_sync_output,
+      parseErrorData: _wire2api_error,
+      constMeta: kRunUntilAudioSamplesConstMeta,
+      argValues: [id, sampleFrames, bufferFill],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kRunUntilAudioSamplesConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "run_until_audio_samples",
+        argNames: ["id", "sampleFrames", "bufferFill"],
+      );
+
+  Future<Uint8List?> saveEmulatorState({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_save_emulator_state(port_, arg0),
+      parseSuccessData: _wire2api_opt_uint_8_list,
+      parseErrorData: _wire2api_error,
+      constMeta: kSaveEmulatorStateConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSaveEmulatorStateConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "save_emulator_state",
+        argNames: ["id"],
+      );
+
+  Future<void> loadEmulatorState(
+      {required int id, required Uint8List state, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = _platform.api2wire_uint_8_list(state);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_load_emulator_state(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_FrbAnyhowException,
+      constMeta: kLoadEmulatorStateConstMeta,
+      argValues: [id, state],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kLoadEmulatorStateConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "load_emulator_state",
+        argNames: ["id", "state"],
+      );
+
+  Future<void> setRewindConfig(
+      {required int id,
+      required int memoryBudget,
+      required int framesPerSnapshot,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u32(memoryBudget);
+    var arg2 = api2wire_u32(framesPerSnapshot);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_set_rewind_config(port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kSetRewindConfigConstMeta,
+      argValues: [id, memoryBudget, framesPerSnapshot],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSetRewindConfigConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "set_rewind_config",
+        argNames: ["id", "memoryBudget", "framesPerSnapshot"],
+      );
+
+  Future<int> rewindEmulator(
+      {required int id, required int frames, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u32(frames);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_rewind_emulator(port_, arg0, arg1),
+      parseSuccessData: _wire2api_u32,
+      parseErrorData: _wire2api_error,
+      constMeta: kRewindEmulatorConstMeta,
+      argValues: [id, frames],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kRewindEmulatorConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "rewind_emulator",
+        argNames: ["id", "frames"],
+      );
+
+  Future<void> setEmulationSpeed(
+      {required int id, required double speed, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_f64(speed);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_set_emulation_speed(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kSetEmulationSpeedConstMeta,
+      argValues: [id, speed],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSetEmulationSpeedConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "set_emulation_speed",
+        argNames: ["id", "speed"],
+      );
+
+  Future<MachineState?> stepEmulatorInstruction(
+      {required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_step_emulator_instruction(port_, arg0),
+      parseSuccessData: _wire2api_opt_machine_state,
+      parseErrorData: _wire2api_error,
+      constMeta: kStepEmulatorInstructionConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStepEmulatorInstructionConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "step_emulator_instruction",
+        argNames: ["id"],
+      );
+
+  Future<MachineState?> stepEmulatorScanline({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_step_emulator_scanline(port_, arg0),
+      parseSuccessData: _wire2api_opt_machine_state,
+      parseErrorData: _wire2api_error,
+      constMeta: kStepEmulatorScanlineConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStepEmulatorScanlineConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "step_emulator_scanline",
+        argNames: ["id"],
+      );
+
+  Future<MachineState?> stepEmulatorFrame({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_step_emulator_frame(port_, arg0),
+      parseSuccessData: _wire2api_opt_machine_state,
+      parseErrorData: _wire2api_error,
+      constMeta: kStepEmulatorFrameConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStepEmulatorFrameConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "step_emulator_frame",
+        argNames: ["id"],
+      );
+
+  Future<Uint32List?> getScreenBuffer({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_get_screen_buffer(port_, arg0),
+      parseSuccessData: _wire2api_opt_uint_32_list,
+      parseErrorData: _wire2api_error,
+      constMeta: kGetScreenBufferConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kGetScreenBufferConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "get_screen_buffer",
+        argNames: ["id"],
+      );
+
+  Future<bool> addEmulatorBreakpoint(
+      {required int id,
+      required int address,
+      int? bank,
+      String? condition,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u16(address);
+    var arg2 = _platform.api2wire_opt_box_autoadd_u16(bank);
+    var arg3 = _platform.api2wire_opt_String(condition);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_add_emulator_breakpoint(
+          port_, arg0, arg1, arg2, arg3),
+      parseSuccessData: _wire2api_bool,
+      parseErrorData: _wire2api_error,
+      constMeta: kAddEmulatorBreakpointConstMeta,
+      argValues: [id, address, bank, condition],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kAddEmulatorBreakpointConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "add_emulator_breakpoint",
+        argNames: ["id", "address", "bank", "condition"],
+      );
+
+  Future<void> removeEmulatorBreakpoint(
+      {required int id, required int address, int? bank, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u16(address);
+    var arg2 = _platform.api2wire_opt_box_autoadd_u16(bank);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_remove_emulator_breakpoint(
+          port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kRemoveEmulatorBreakpointConstMeta,
+      argValues: [id, address, bank],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kRemoveEmulatorBreakpointConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "remove_emulator_breakpoint",
+        argNames: ["id", "address", "bank"],
+      );
+
+  Future<void> addEmulatorWatchpoint(
+      {required int id,
+      required int start,
+      required int end,
+      required bool read,
+      required bool write,
+      required bool execute,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u16(start);
+    var arg2 = api2wire_u16(end);
+    var arg3 = api2wire_bool(read);
+    var arg4 = api2wire_bool(write);
+    var arg5 = api2wire_bool(execute);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_add_emulator_watchpoint(
+          port_, arg0, arg1, arg2, arg3, arg4, arg5),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kAddEmulatorWatchpointConstMeta,
+      argValues: [id, start, end, read, write, execute],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kAddEmulatorWatchpointConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "add_emulator_watchpoint",
+        argNames: ["id", "start", "end", "read", "write", "execute"],
+      );
+
+  Future<void> removeEmulatorWatchpoint(
+      {required int id, required int start, required int end, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u16(start);
+    var arg2 = api2wire_u16(end);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_remove_emulator_watchpoint(
+          port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kRemoveEmulatorWatchpointConstMeta,
+      argValues: [id, start, end],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kRemoveEmulatorWatchpointConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "remove_emulator_watchpoint",
+        argNames: ["id", "start", "end"],
+      );
+
+  Future<void> clearEmulatorBreakpoints({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_clear_emulator_breakpoints(port_, arg0),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kClearEmulatorBreakpointsConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kClearEmulatorBreakpointsConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "clear_emulator_breakpoints",
+        argNames: ["id"],
+      );
+
+  Future<void> setEmulatorBreakOnInterrupt(
+      {required int id, required bool enabled, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_bool(enabled);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_set_emulator_break_on_interrupt(
+          port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kSetEmulatorBreakOnInterruptConstMeta,
+      argValues: [id, enabled],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSetEmulatorBreakOnInterruptConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "set_emulator_break_on_interrupt",
+        argNames: ["id", "enabled"],
+      );
+
+  Future<StopReason?> getStopReason({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_get_stop_reason(port_, arg0),
+      parseSuccessData: _wire2api_opt_stop_reason,
+      parseErrorData: _wire2api_error,
+      constMeta: kGetStopReasonConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kGetStopReasonConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "get_stop_reason",
+        argNames: ["id"],
+      );
+
+  Future<void> resumeEmulator({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
     return _platform.executeNormal(FlutterRustBridgeTask(
-      callFfi: (port_) => _platform.inner.wire_load(port_, arg0, arg1),
+      callFfi: (port_) => _platform.inner.wire_resume_emulator(port_, arg0),
       parseSuccessData: _wire2api_unit,
       parseErrorData: _wire2api_error,
-      constMeta: kLoadConstMeta,
-      argValues: [romData, ramData],
+      constMeta: kResumeEmulatorConstMeta,
+      argValues: [id],
       hint: hint,
     ));
   }
 
-  FlutterRustBridgeTaskConstMeta get kLoadConstMeta =>
-      const FlutterRustBridgeTaskConstMeta(
-        debugName: "load",
-        argNames: ["romData", "ramData"],
-      );
+  FlutterRustBridgeTaskConstMeta get kResumeEmulatorConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "resume_emulator",
+        argNames: ["id"],
+      );
+
+  Future<List<Instruction>?> disassembleMemory(
+      {required int id,
+      required int address,
+      required int count,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u16(address);
+    var arg2 = api2wire_u32(count);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_disassemble_memory(port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_opt_list_instruction,
+      parseErrorData: _wire2api_error,
+      constMeta: kDisassembleMemoryConstMeta,
+      argValues: [id, address, count],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kDisassembleMemoryConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "disassemble_memory",
+        argNames: ["id", "address", "count"],
+      );
+
+  Future<bool> startEmulatorTraceFile(
+      {required int id, required String path, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = _platform.api2wire_String(path);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_start_emulator_trace_file(port_, arg0, arg1),
+      parseSuccessData: _wire2api_bool,
+      parseErrorData: _wire2api_error,
+      constMeta: kStartEmulatorTraceFileConstMeta,
+      argValues: [id, path],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStartEmulatorTraceFileConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "start_emulator_trace_file",
+        argNames: ["id", "path"],
+      );
+
+  Future<void> startEmulatorTraceBuffer(
+      {required int id, required int capacity, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u32(capacity);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_start_emulator_trace_buffer(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kStartEmulatorTraceBufferConstMeta,
+      argValues: [id, capacity],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStartEmulatorTraceBufferConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "start_emulator_trace_buffer",
+        argNames: ["id", "capacity"],
+      );
+
+  Future<void> stopEmulatorTrace({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_stop_emulator_trace(port_, arg0),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kStopEmulatorTraceConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStopEmulatorTraceConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "stop_emulator_trace",
+        argNames: ["id"],
+      );
+
+  Future<String?> getEmulatorTrace({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_get_emulator_trace(port_, arg0),
+      parseSuccessData: _wire2api_opt_String,
+      parseErrorData: _wire2api_error,
+      constMeta: kGetEmulatorTraceConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kGetEmulatorTraceConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "get_emulator_trace",
+        argNames: ["id"],
+      );
+
+  Future<bool> startGdbServer(
+      {required int id, required int port, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u16(port);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_start_gdb_server(port_, arg0, arg1),
+      parseSuccessData: _wire2api_bool,
+      parseErrorData: _wire2api_error,
+      constMeta: kStartGdbServerConstMeta,
+      argValues: [id, port],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStartGdbServerConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "start_gdb_server",
+        argNames: ["id", "port"],
+      );
+
+  Future<void> stopGdbServer({dynamic hint}) {
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_stop_gdb_server(port_),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kStopGdbServerConstMeta,
+      argValues: [],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStopGdbServerConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "stop_gdb_server",
+        argNames: [],
+      );
+
+  Future<int> create(
+      {required Uint8List romData,
+      Uint8List? ramData,
+      Uint8List? bootRom,
+      required int model,
+      dynamic hint}) {
+    var arg0 = _platform.api2wire_uint_8_list(romData);
+    var arg1 = _platform.api2wire_opt_uint_8_list(ramData);
+    var arg2 = _platform.api2wire_opt_uint_8_list(bootRom);
+    var arg3 = api2wire_u8(model);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_create(port_, arg0, arg1, arg2, arg3),
+      parseSuccessData: _wire2api_u32,
+      parseErrorData: _wire2api_FrbAnyhowException,
+      constMeta: kCreateConstMeta,
+      argValues: [romData, ramData, bootRom, model],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kCreateConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "create",
+        argNames: ["romData", "ramData", "bootRom", "model"],
+      );
+
+  Future<Uint8List?> destroy({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_destroy(port_, arg0),
+      parseSuccessData: _wire2api_opt_uint_8_list,
+      parseErrorData: _wire2api_error,
+      constMeta: kDestroyConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kDestroyConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "destroy",
+        argNames: ["id"],
+      );
+
+  Future<FrameOutput> render({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_render(port_, arg0),
+      parseSuccessData: _wire2api_frame_output,
+      parseErrorData: _wire2api_FrbAnyhowException,
+      constMeta: kRenderConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kRenderConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "render",
+        argNames: ["id"],
+      );
+
+  Future<void> setButtons(
+      {required int id, required Uint8List buttonStates, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = _platform.api2wire_uint_8_list(buttonStates);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_set_buttons(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kSetButtonsConstMeta,
+      argValues: [id, buttonStates],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSetButtonsConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "set_buttons",
+        argNames: ["id", "buttonStates"],
+      );
+
+  Future<void> setSampleRate(
+      {required int id, required int sampleRate, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u32(sampleRate);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_set_sample_rate(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kSetSampleRateConstMeta,
+      argValues: [id, sampleRate],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSetSampleRateConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "set_sample_rate",
+        argNames: ["id", "sampleRate"],
+      );
+
+  Future<Int16List?> drainAudio({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_drain_audio(port_, arg0),
+      parseSuccessData: _wire2api_opt_int_16_list,
+      parseErrorData: _wire2api_error,
+      constMeta: kDrainAudioConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kDrainAudioConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "drain_audio",
+        argNames: ["id"],
+      );
+
+  Future<void> setCgbAudioFilter(
+      {required int id, required bool enabled, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_bool(enabled);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_set_cgb_audio_filter(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kSetCgbAudioFilterConstMeta,
+      argValues: [id, enabled],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSetCgbAudioFilterConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "set_cgb_audio_filter",
+        argNames: ["id", "enabled"],
+      );
+
+  Future<void> muteChannel(
+      {required int id,
+      required int channel,
+      required bool muted,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u8(channel);
+    var arg2 = api2wire_bool(muted);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_mute_channel(port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kMuteChannelConstMeta,
+      argValues: [id, channel, muted],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kMuteChannelConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "mute_channel",
+        argNames: ["id", "channel", "muted"],
+      );
+
+  Future<void> soloChannel(
+      {required int id,
+      required int channel,
+      required bool soloed,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u8(channel);
+    var arg2 = api2wire_bool(soloed);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_solo_channel(port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kSoloChannelConstMeta,
+      argValues: [id, channel, soloed],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSoloChannelConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "solo_channel",
+        argNames: ["id", "channel", "soloed"],
+      );
+
+  Future<List<ChannelState>?> channelStates({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_channel_states(port_, arg0),
+      parseSuccessData: _wire2api_opt_list_channel_state,
+      parseErrorData: _wire2api_error,
+      constMeta: kChannelStatesConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kChannelStatesConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "channel_states",
+        argNames: ["id"],
+      );
+
+  Future<void> startRecording({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_start_recording(port_, arg0),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kStartRecordingConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStartRecordingConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "start_recording",
+        argNames: ["id"],
+      );
+
+  Future<Uint8List?> stopRecording({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_stop_recording(port_, arg0),
+      parseSuccessData: _wire2api_opt_uint_8_list,
+      parseErrorData: _wire2api_error,
+      constMeta: kStopRecordingConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStopRecordingConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "stop_recording",
+        argNames: ["id"],
+      );
+
+  Future<AudioSyncOutput?> renderAudio(
+      {required int id,
+      required int sampleFrames,
+      required double bufferFill,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u32(sampleFrames);
+    var arg2 = api2wire_f32(bufferFill);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_render_audio(port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_opt_audio_sync_output,
+      parseErrorData: _wire2api_error,
+      constMeta: kRenderAudioConstMeta,
+      argValues: [id, sampleFrames, bufferFill],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kRenderAudioConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "render_audio",
+        argNames: ["id", "sampleFrames", "bufferFill"],
+      );
+
+  Future<Uint8List?> saveState({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_save_state(port_, arg0),
+      parseSuccessData: _wire2api_opt_uint_8_list,
+      parseErrorData: _wire2api_error,
+      constMeta: kSaveStateConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSaveStateConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "save_state",
+        argNames: ["id"],
+      );
+
+  Future<void> loadState(
+      {required int id, required Uint8List state, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = _platform.api2wire_uint_8_list(state);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_load_state(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_FrbAnyhowException,
+      constMeta: kLoadStateConstMeta,
+      argValues: [id, state],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kLoadStateConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "load_state",
+        argNames: ["id", "state"],
+      );
+
+  Future<void> configureRewind(
+      {required int id,
+      required int memoryBudget,
+      required int framesPerSnapshot,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u32(memoryBudget);
+    var arg2 = api2wire_u32(framesPerSnapshot);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_configure_rewind(port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kConfigureRewindConstMeta,
+      argValues: [id, memoryBudget, framesPerSnapshot],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kConfigureRewindConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "configure_rewind",
+        argNames: ["id", "memoryBudget", "framesPerSnapshot"],
+      );
+
+  Future<int> rewind({required int id, required int frames, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u32(frames);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_rewind(port_, arg0, arg1),
+      parseSuccessData: _wire2api_u32,
+      parseErrorData: _wire2api_error,
+      constMeta: kRewindConstMeta,
+      argValues: [id, frames],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kRewindConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "rewind",
+        argNames: ["id", "frames"],
+      );
+
+  Future<void> setSpeed(
+      {required int id, required double multiplier, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_f64(multiplier);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_set_speed(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kSetSpeedConstMeta,
+      argValues: [id, multiplier],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kSetSpeedConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "set_speed",
+        argNames: ["id", "multiplier"],
+      );
+
+  Future<MachineState?> stepInstruction({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_step_instruction(port_, arg0),
+      parseSuccessData: _wire2api_opt_machine_state,
+      parseErrorData: _wire2api_error,
+      constMeta: kStepInstructionConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStepInstructionConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "step_instruction",
+        argNames: ["id"],
+      );
+
+  Future<MachineState?> stepScanline({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_step_scanline(port_, arg0),
+      parseSuccessData: _wire2api_opt_machine_state,
+      parseErrorData: _wire2api_error,
+      constMeta: kStepScanlineConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStepScanlineConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "step_scanline",
+        argNames: ["id"],
+      );
+
+  Future<MachineState?> stepFrame({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_step_frame(port_, arg0),
+      parseSuccessData: _wire2api_opt_machine_state,
+      parseErrorData: _wire2api_error,
+      constMeta: kStepFrameConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStepFrameConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "step_frame",
+        argNames: ["id"],
+      );
+
+  Future<Uint32List?> currentFrame({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_current_frame(port_, arg0),
+      parseSuccessData: _wire2api_opt_uint_32_list,
+      parseErrorData: _wire2api_error,
+      constMeta: kCurrentFrameConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kCurrentFrameConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "current_frame",
+        argNames: ["id"],
+      );
+
+  Future<bool> addBreakpoint(
+      {required int id,
+      required int address,
+      int? bank,
+      String? condition,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u16(address);
+    var arg2 = _platform.api2wire_opt_box_autoadd_u16(bank);
+    var arg3 = _platform.api2wire_opt_String(condition);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_add_breakpoint(port_, arg0, arg1, arg2, arg3),
+      parseSuccessData: _wire2api_bool,
+      parseErrorData: _wire2api_error,
+      constMeta: kAddBreakpointConstMeta,
+      argValues: [id, address, bank, condition],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kAddBreakpointConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "add_breakpoint",
+        argNames: ["id", "address", "bank", "condition"],
+      );
+
+  Future<void> removeBreakpoint(
+      {required int id, required int address, int? bank, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u16(address);
+    var arg2 = _platform.api2wire_opt_box_autoadd_u16(bank);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_remove_breakpoint(port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kRemoveBreakpointConstMeta,
+      argValues: [id, address, bank],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kRemoveBreakpointConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "remove_breakpoint",
+        argNames: ["id", "address", "bank"],
+      );
+
+  Future<void> addWatchpoint(
+      {required int id,
+      required int start,
+      required int end,
+      required bool read,
+      required bool write,
+      required bool execute,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u16(start);
+    var arg2 = api2wire_u16(end);
+    var arg3 = api2wire_bool(read);
+    var arg4 = api2wire_bool(write);
+    var arg5 = api2wire_bool(execute);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_add_watchpoint(
+          port_, arg0, arg1, arg2, arg3, arg4, arg5),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kAddWatchpointConstMeta,
+      argValues: [id, start, end, read, write, execute],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kAddWatchpointConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "add_watchpoint",
+        argNames: ["id", "start", "end", "read", "write", "execute"],
+      );
+
+  Future<void> removeWatchpoint(
+      {required int id, required int start, required int end, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u16(start);
+    var arg2 = api2wire_u16(end);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_remove_watchpoint(port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kRemoveWatchpointConstMeta,
+      argValues: [id, start, end],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kRemoveWatchpointConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "remove_watchpoint",
+        argNames: ["id", "start", "end"],
+      );
+
+  Future<void> clearBreakpoints({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_clear_breakpoints(port_, arg0),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kClearBreakpointsConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kClearBreakpointsConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "clear_breakpoints",
+        argNames: ["id"],
+      );
+
+  Future<void> breakOnInterrupt(
+      {required int id, required bool enabled, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_bool(enabled);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_break_on_interrupt(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kBreakOnInterruptConstMeta,
+      argValues: [id, enabled],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kBreakOnInterruptConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "break_on_interrupt",
+        argNames: ["id", "enabled"],
+      );
+
+  Future<StopReason?> stopReason({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_stop_reason(port_, arg0),
+      parseSuccessData: _wire2api_opt_stop_reason,
+      parseErrorData: _wire2api_error,
+      constMeta: kStopReasonConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStopReasonConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "stop_reason",
+        argNames: ["id"],
+      );
+
+  Future<void> resume({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_resume(port_, arg0),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kResumeConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kResumeConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "resume",
+        argNames: ["id"],
+      );
+
+  Future<List<Instruction>?> disassemble(
+      {required int id,
+      required int address,
+      required int count,
+      dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u16(address);
+    var arg2 = api2wire_u32(count);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_disassemble(port_, arg0, arg1, arg2),
+      parseSuccessData: _wire2api_opt_list_instruction,
+      parseErrorData: _wire2api_error,
+      constMeta: kDisassembleConstMeta,
+      argValues: [id, address, count],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kDisassembleConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "disassemble",
+        argNames: ["id", "address", "count"],
+      );
+
+  Future<bool> traceToFile(
+      {required int id, required String path, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = _platform.api2wire_String(path);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_trace_to_file(port_, arg0, arg1),
+      parseSuccessData: _wire2api_bool,
+      parseErrorData: _wire2api_error,
+      constMeta: kTraceToFileConstMeta,
+      argValues: [id, path],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kTraceToFileConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "trace_to_file",
+        argNames: ["id", "path"],
+      );
+
+  Future<void> traceToBuffer(
+      {required int id, required int lines, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u32(lines);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) =>
+          _platform.inner.wire_trace_to_buffer(port_, arg0, arg1),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kTraceToBufferConstMeta,
+      argValues: [id, lines],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kTraceToBufferConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "trace_to_buffer",
+        argNames: ["id", "lines"],
+      );
+
+  Future<void> stopTrace({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_stop_trace(port_, arg0),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kStopTraceConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStopTraceConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "stop_trace",
+        argNames: ["id"],
+      );
+
+  Future<String?> traceLog({required int id, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_trace_log(port_, arg0),
+      parseSuccessData: _wire2api_opt_String,
+      parseErrorData: _wire2api_error,
+      constMeta: kTraceLogConstMeta,
+      argValues: [id],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kTraceLogConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "trace_log",
+        argNames: ["id"],
+      );
+
+  Future<bool> startGdb({required int id, required int port, dynamic hint}) {
+    var arg0 = api2wire_u32(id);
+    var arg1 = api2wire_u16(port);
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_start_gdb(port_, arg0, arg1),
+      parseSuccessData: _wire2api_bool,
+      parseErrorData: _wire2api_error,
+      constMeta: kStartGdbConstMeta,
+      argValues: [id, port],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStartGdbConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "start_gdb",
+        argNames: ["id", "port"],
+      );
+
+  Future<void> stopGdb({dynamic hint}) {
+    return _platform.executeNormal(FlutterRustBridgeTask(
+      callFfi: (port_) => _platform.inner.wire_stop_gdb(port_),
+      parseSuccessData: _wire2api_unit,
+      parseErrorData: _wire2api_error,
+      constMeta: kStopGdbConstMeta,
+      argValues: [],
+      hint: hint,
+    ));
+  }
+
+  FlutterRustBridgeTaskConstMeta get kStopGdbConstMeta =>
+      const FlutterRustBridgeTaskConstMeta(
+        debugName: "stop_gdb",
+        argNames: [],
+      );
+
+  void dispose() {
+    _platform.dispose();
+  }
+// Section: wire2api
+
+  FrbAnyhowException _wire2api_FrbAnyhowException(dynamic raw) {
+    return FrbAnyhowException(raw as String);
+  }
+
+  String _wire2api_String(dynamic raw) {
+    return raw as String;
+  }
+
+  AudioSyncOutput _wire2api_audio_sync_output(dynamic raw) {
+    final arr = raw as List<dynamic>;
+    if (arr.length != 3)
+      throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+    return AudioSyncOutput(
+      frame: _wire2api_uint_32_list(arr[0]),
+      samples: _wire2api_int_16_list(arr[1]),
+      stopReason: _wire2api_opt_stop_reason(arr[2]),
+    );
+  }
+
+  bool _wire2api_bool(dynamic raw) {
+    return raw as bool;
+  }
+
+  ChannelState _wire2api_channel_state(dynamic raw) {
+    final arr = raw as List<dynamic>;
+    if (arr.length != 5)
+      throw Exception('unexpected arr length: expect 5 but see ${arr.length}');
+    return ChannelState(
+      enabled: _wire2api_bool(arr[0]),
+      frequencyHz: _wire2api_f64(arr[1]),
+      volume: _wire2api_u8(arr[2]),
+      duty: _wire2api_u8(arr[3]),
+      lengthRemaining: _wire2api_u16(arr[4]),
+    );
+  }
+
+  double _wire2api_f64(dynamic raw) {
+    return raw as double;
+  }
+
+  FrameOutput _wire2api_frame_output(dynamic raw) {
+    final arr = raw as List<dynamic>;
+    if (arr.length != 2)
+      throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+    return FrameOutput(
+      frame: _wire2api_uint_32_list(arr[0]),
+      stopReason: _wire2api_opt_stop_reason(arr[1]),
+    );
+  }
+
+  int _wire2api_i16(dynamic raw) {
+    return raw as int;
+  }
+
+  Instruction _wire2api_instruction(dynamic raw) {
+    final arr = raw as List<dynamic>;
+    if (arr.length != 6)
+      throw Exception('unexpected arr length: expect 6 but see ${arr.length}');
+    return Instruction(
+      address: _wire2api_u16(arr[0]),
+      bytes: _wire2api_uint_8_list(arr[1]),
+      text: _wire2api_String(arr[2]),
+      length: _wire2api_u8(arr[3]),
+      cycles: _wire2api_u8(arr[4]),
+      cyclesTaken: _wire2api_u8(arr[5]),
+    );
+  }
+
+  Int16List _wire2api_int_16_list(dynamic raw) {
+    return raw as Int16List;
+  }
+
+  List<ChannelState> _wire2api_list_channel_state(dynamic raw) {
+    return (raw as List<dynamic>).map(_wire2api_channel_state).toList();
+  }
+
+  List<Instruction> _wire2api_list_instruction(dynamic raw) {
+    return (raw as List<dynamic>).map(_wire2api_instruction).toList();
+  }
+
+  MachineState _wire2api_machine_state(dynamic raw) {
+    final arr = raw as List<dynamic>;
+    if (arr.length != 14)
+      throw Exception('unexpected arr length: expect 14 but see ${arr.length}');
+    return MachineState(
+      a: _wire2api_u8(arr[0]),
+      b: _wire2api_u8(arr[1]),
+      c: _wire2api_u8(arr[2]),
+      d: _wire2api_u8(arr[3]),
+      e: _wire2api_u8(arr[4]),
+      f: _wire2api_u8(arr[5]),
+      h: _wire2api_u8(arr[6]),
+      l: _wire2api_u8(arr[7]),
+      pc: _wire2api_u16(arr[8]),
+      sp: _wire2api_u16(arr[9]),
+      ime: _wire2api_bool(arr[10]),
+      halted: _wire2api_bool(arr[11]),
+      ly: _wire2api_u8(arr[12]),
+      ppuMode: _wire2api_u8(arr[13]),
+    );
+  }
+
+  String? _wire2api_opt_String(dynamic raw) {
+    return raw == null ? null : _wire2api_String(raw);
+  }
+
+  AudioSyncOutput? _wire2api_opt_audio_sync_output(dynamic raw) {
+    return raw == null ? null : _wire2api_audio_sync_output(raw);
+  }
+
+  Int16List? _wire2api_opt_int_16_list(dynamic raw) {
+    return raw == null ? null : _wire2api_int_16_list(raw);
+  }
+
+  List<ChannelState>? _wire2api_opt_list_channel_state(dynamic raw) {
+    return raw == null ? null : _wire2api_list_channel_state(raw);
+  }
+
+  List<Instruction>? _wire2api_opt_list_instruction(dynamic raw) {
+    return raw == null ? null : _wire2api_list_instruction(raw);
+  }
+
+  MachineState? _wire2api_opt_machine_state(dynamic raw) {
+    return raw == null ? null : _wire2api_machine_state(raw);
+  }
+
+  StopReason? _wire2api_opt_stop_reason(dynamic raw) {
+    return raw == null ? null : _wire2api_stop_reason(raw);
+  }
+
+  Uint32List? _wire2api_opt_uint_32_list(dynamic raw) {
+    return raw == null ? null : _wire2api_uint_32_list(raw);
+  }
+
+  Uint8List? _wire2api_opt_uint_8_list(dynamic raw) {
+    return raw == null ? null : _wire2api_uint_8_list(raw);
+  }
+
+  StopKind _wire2api_stop_kind(dynamic raw) {
+    return StopKind.values[raw as int];
+  }
+
+  StopReason _wire2api_stop_reason(dynamic raw) {
+    final arr = raw as List<dynamic>;
+    if (arr.length != 2)
+      throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+    return StopReason(
+      kind: _wire2api_stop_kind(arr[0]),
+      address: _wire2api_u16(arr[1]),
+    );
+  }
+
+  int _wire2api_u16(dynamic raw) {
+    return raw as int;
+  }
+
+  int _wire2api_u32(dynamic raw) {
+    return raw as int;
+  }
+
+  int _wire2api_u8(dynamic raw) {
+    return raw as int;
+  }
+
+  Object _wire2api_error(dynamic raw) {
+    return raw;
+  }
+
+  Uint32List _wire2api_uint_32_list(dynamic raw) {
+    return raw as Uint32List;
+  }
+
+  Uint8List _wire2api_uint_8_list(dynamic raw) {
+    return raw as Uint8List;
+  }
+
+  void _wire2api_unit(dynamic raw) {
+    return;
+  }
+}
+
+// Section: api2wire
+
+@protected
+bool api2wire_bool(bool raw) {
+  return raw;
+}
+
+@protected
+double api2wire_f32(double raw) {
+  return raw;
+}
+
+@protected
+double api2wire_f64(double raw) {
+  return raw;
+}
+
+@protected
+int api2wire_u16(int raw) {
+  return raw;
+}
+
+@protected
+int api2wire_u32(int raw) {
+  return raw;
+}
+
+@protected
+int api2wire_u8(int raw) {
+  return raw;
+}
+
+// Section: finalizer
+
+class RustCorePlatform extends FlutterRustBridgeBase<RustCoreWire> {
+  RustCorePlatform(ffi.DynamicLibrary dylib) : super(RustCoreWire(dylib));
+
+// Section: api2wire
+
+  @protected
+  ffi.Pointer<wire_uint_8_list> api2wire_String(String raw) {
+    return api2wire_uint_8_list(utf8.encoder.convert(raw));
+  }
+
+  @protected
+  ffi.Pointer<ffi.Uint16> api2wire_box_autoadd_u16(int raw) {
+    return inner.new_box_autoadd_u16_0(api2wire_u16(raw));
+  }
+
+  @protected
+  ffi.Pointer<wire_uint_8_list> api2wire_opt_String(String? raw) {
+    return raw == null ? ffi.nullptr : api2wire_String(raw);
+  }
+
+  @protected
+  ffi.Pointer<ffi.Uint16> api2wire_opt_box_autoadd_u16(int? raw) {
+    return raw == null ? ffi.nullptr : api2wire_box_autoadd_u16(raw);
+  }
+
+  @protected
+  ffi.Pointer<wire_uint_8_list> api2wire_opt_uint_8_list(Uint8List? raw) {
+    return raw == null ? ffi.nullptr : api2wire_uint_8_list(raw);
+  }
+
+  @protected
+  ffi.Pointer<wire_uint_8_list> api2wire_uint_8_list(Uint8List raw) {
+    final ans = inner.new_uint_8_list_0(raw.length);
+    ans.ref.ptr.asTypedList(raw.length).setAll(0, raw);
+    return ans;
+  }
+// Section: finalizer
+
+// Section: api_fill_to_wire
+}
+
+// ignore_for_file: camel_case_types, non_constant_identifier_names, avoid_positional_boolean_parameters, annotate_overrides, constant_identifier_names
+
+// AUTO GENERATED FILE, DO NOT EDIT.
+//
+// Generated by `package:ffigen`.
+// ignore_for_file: type=lint
+
+/// generated by flutter_rust_bridge
+class RustCoreWire implements FlutterRustBridgeWireBase {
+  @internal
+  late final dartApi = DartApiDl(init_frb_dart_api_dl);
+
+  /// Holds the symbol lookup function.
+  final ffi.Pointer<T> Function<T extends ffi.NativeType>(String symbolName)
+      _lookup;
+
+  /// The symbols are looked up in [dynamicLibrary].
+  RustCoreWire(ffi.DynamicLibrary dynamicLibrary)
+      : _lookup = dynamicLibrary.lookup;
+
+  /// The symbols are looked up with [lookup].
+  RustCoreWire.fromLookup(
+      ffi.Pointer<T> Function<T extends ffi.NativeType>(String symbolName)
+          lookup)
+      : _lookup = lookup;
+
+  void store_dart_post_cobject(
+    DartPostCObjectFnType ptr,
+  ) {
+    return _store_dart_post_cobject(
+      ptr,
+    );
+  }
+
+  late final _store_dart_post_cobjectPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(DartPostCObjectFnType)>>(
+          'store_dart_post_cobject');
+  late final _store_dart_post_cobject = _store_dart_post_cobjectPtr
+      .asFunction<void Function(DartPostCObjectFnType)>();
+
+  Object get_dart_object(
+    int ptr,
+  ) {
+    return _get_dart_object(
+      ptr,
+    );
+  }
+
+  late final _get_dart_objectPtr =
+      _lookup<ffi.NativeFunction<ffi.Handle Function(ffi.UintPtr)>>(
+          'get_dart_object');
+  late final _get_dart_object =
+      _get_dart_objectPtr.asFunction<Object Function(int)>();
+
+  void drop_dart_object(
+    int ptr,
+  ) {
+    return _drop_dart_object(
+      ptr,
+    );
+  }
+
+  late final _drop_dart_objectPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.UintPtr)>>(
+          'drop_dart_object');
+  late final _drop_dart_object =
+      _drop_dart_objectPtr.asFunction<void Function(int)>();
+
+  int new_dart_opaque(
+    Object handle,
+  ) {
+    return _new_dart_opaque(
+      handle,
+    );
+  }
+
+  late final _new_dart_opaquePtr =
+      _lookup<ffi.NativeFunction<ffi.UintPtr Function(ffi.Handle)>>(
+          'new_dart_opaque');
+  late final _new_dart_opaque =
+      _new_dart_opaquePtr.asFunction<int Function(Object)>();
+
+  int init_frb_dart_api_dl(
+    ffi.Pointer<ffi.Void> obj,
+  ) {
+    return _init_frb_dart_api_dl(
+      obj,
+    );
+  }
+
+  late final _init_frb_dart_api_dlPtr =
+      _lookup<ffi.NativeFunction<ffi.IntPtr Function(ffi.Pointer<ffi.Void>)>>(
+          'init_frb_dart_api_dl');
+  late final _init_frb_dart_api_dl = _init_frb_dart_api_dlPtr
+      .asFunction<int Function(ffi.Pointer<ffi.Void>)>();
+
+  ffi.Pointer<ffi.Uint16> new_box_autoadd_u16_0(
+    int value,
+  ) {
+    return _new_box_autoadd_u16_0(
+      value,
+    );
+  }
+
+  late final _new_box_autoadd_u16_0Ptr =
+      _lookup<ffi.NativeFunction<ffi.Pointer<ffi.Uint16> Function(ffi.Uint16)>>(
+          'new_box_autoadd_u16_0');
+  late final _new_box_autoadd_u16_0 = _new_box_autoadd_u16_0Ptr
+      .asFunction<ffi.Pointer<ffi.Uint16> Function(int)>();
+
+  void wire_create_emulator(
+    int port_,
+    ffi.Pointer<wire_uint_8_list> rom_data,
+    ffi.Pointer<wire_uint_8_list> ram_data,
+    ffi.Pointer<wire_uint_8_list> boot_rom,
+    int model,
+  ) {
+    return _wire_create_emulator(
+      port_,
+      rom_data,
+      ram_data,
+      boot_rom,
+      model,
+    );
+  }
+
+  late final _wire_create_emulatorPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Pointer<wire_uint_8_list>, ffi.Pointer<wire_uint_8_list>, ffi.Pointer<wire_uint_8_list>, ffi.Uint8)>>(
+          'wire_create_emulator');
+  late final _wire_create_emulator =
+      _wire_create_emulatorPtr.asFunction<void Function(int, ffi.Pointer<wire_uint_8_list>, ffi.Pointer<wire_uint_8_list>, ffi.Pointer<wire_uint_8_list>, int)>();
+
+  void wire_destroy_emulator(
+    int port_,
+    int id,
+  ) {
+    return _wire_destroy_emulator(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_destroy_emulatorPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_destroy_emulator');
+  late final _wire_destroy_emulator =
+      _wire_destroy_emulatorPtr.asFunction<void Function(int, int)>();
+
+  void wire_render_frame(
+    int port_,
+    int id,
+  ) {
+    return _wire_render_frame(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_render_framePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_render_frame');
+  late final _wire_render_frame =
+      _wire_render_framePtr.asFunction<void Function(int, int)>();
+
+  void wire_set_buttons_state(
+    int port_,
+    int id,
+    ffi.Pointer<wire_uint_8_list> button_states,
+  ) {
+    return _wire_set_buttons_state(
+      port_,
+      id,
+      button_states,
+    );
+  }
+
+  late final _wire_set_buttons_statePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Pointer<wire_uint_8_list>)>>(
+          'wire_set_buttons_state');
+  late final _wire_set_buttons_state =
+      _wire_set_buttons_statePtr.asFunction<void Function(int, int, ffi.Pointer<wire_uint_8_list>)>();
+
+  void wire_set_audio_sample_rate(
+    int port_,
+    int id,
+    int sample_rate,
+  ) {
+    return _wire_set_audio_sample_rate(
+      port_,
+      id,
+      sample_rate,
+    );
+  }
+
+  late final _wire_set_audio_sample_ratePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint32)>>(
+          'wire_set_audio_sample_rate');
+  late final _wire_set_audio_sample_rate =
+      _wire_set_audio_sample_ratePtr.asFunction<void Function(int, int, int)>();
+
+  void wire_drain_audio_samples(
+    int port_,
+    int id,
+  ) {
+    return _wire_drain_audio_samples(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_drain_audio_samplesPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_drain_audio_samples');
+  late final _wire_drain_audio_samples =
+      _wire_drain_audio_samplesPtr.asFunction<void Function(int, int)>();
+
+  void wire_set_audio_filter_model(
+    int port_,
+    int id,
+    bool cgb,
+  ) {
+    return _wire_set_audio_filter_model(
+      port_,
+      id,
+      cgb,
+    );
+  }
+
+  late final _wire_set_audio_filter_modelPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Bool)>>(
+          'wire_set_audio_filter_model');
+  late final _wire_set_audio_filter_model =
+      _wire_set_audio_filter_modelPtr.asFunction<void Function(int, int, bool)>();
+
+  void wire_set_audio_channel_muted(
+    int port_,
+    int id,
+    int channel,
+    bool muted,
+  ) {
+    return _wire_set_audio_channel_muted(
+      port_,
+      id,
+      channel,
+      muted,
+    );
+  }
+
+  late final _wire_set_audio_channel_mutedPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint8, ffi.Bool)>>(
+          'wire_set_audio_channel_muted');
+  late final _wire_set_audio_channel_muted =
+      _wire_set_audio_channel_mutedPtr.asFunction<void Function(int, int, int, bool)>();
+
+  void wire_set_audio_channel_soloed(
+    int port_,
+    int id,
+    int channel,
+    bool soloed,
+  ) {
+    return _wire_set_audio_channel_soloed(
+      port_,
+      id,
+      channel,
+      soloed,
+    );
+  }
+
+  late final _wire_set_audio_channel_soloedPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint8, ffi.Bool)>>(
+          'wire_set_audio_channel_soloed');
+  late final _wire_set_audio_channel_soloed =
+      _wire_set_audio_channel_soloedPtr.asFunction<void Function(int, int, int, bool)>();
+
+  void wire_get_audio_channel_states(
+    int port_,
+    int id,
+  ) {
+    return _wire_get_audio_channel_states(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_get_audio_channel_statesPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_get_audio_channel_states');
+  late final _wire_get_audio_channel_states =
+      _wire_get_audio_channel_statesPtr.asFunction<void Function(int, int)>();
+
+  void wire_start_audio_recording(
+    int port_,
+    int id,
+  ) {
+    return _wire_start_audio_recording(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_start_audio_recordingPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_start_audio_recording');
+  late final _wire_start_audio_recording =
+      _wire_start_audio_recordingPtr.asFunction<void Function(int, int)>();
+
+  void wire_stop_audio_recording(
+    int port_,
+    int id,
+  ) {
+    return _wire_stop_audio_recording(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_stop_audio_recordingPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_stop_audio_recording');
+  late final _wire_stop_audio_recording =
+      _wire_stop_audio_recordingPtr.asFunction<void Function(int, int)>();
+
+  void wire_run_until_audio_samples(
+    int port_,
+    int id,
+    int sample_frames,
+    double buffer_fill,
+  ) {
+    return _wire_run_until_audio_samples(
+      port_,
+      id,
+      sample_frames,
+      buffer_fill,
+    );
+  }
+
+  late final _wire_run_until_audio_samplesPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint32, ffi.Float)>>(
+          'wire_run_until_audio_samples');
+  late final _wire_run_until_audio_samples =
+      _wire_run_until_audio_samplesPtr.asFunction<void Function(int, int, int, double)>();
+
+  void wire_save_emulator_state(
+    int port_,
+    int id,
+  ) {
+    return _wire_save_emulator_state(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_save_emulator_statePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_save_emulator_state');
+  late final _wire_save_emulator_state =
+      _wire_save_emulator_statePtr.asFunction<void Function(int, int)>();
+
+  void wire_load_emulator_state(
+    int port_,
+    int id,
+    ffi.Pointer<wire_uint_8_list> state,
+  ) {
+    return _wire_load_emulator_state(
+      port_,
+      id,
+      state,
+    );
+  }
+
+  late final _wire_load_emulator_statePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Pointer<wire_uint_8_list>)>>(
+          'wire_load_emulator_state');
+  late final _wire_load_emulator_state =
+      _wire_load_emulator_statePtr.asFunction<void Function(int, int, ffi.Pointer<wire_uint_8_list>)>();
+
+  void wire_set_rewind_config(
+    int port_,
+    int id,
+    int memory_budget,
+    int frames_per_snapshot,
+  ) {
+    return _wire_set_rewind_config(
+      port_,
+      id,
+      memory_budget,
+      frames_per_snapshot,
+    );
+  }
+
+  late final _wire_set_rewind_configPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint32, ffi.Uint32)>>(
+          'wire_set_rewind_config');
+  late final _wire_set_rewind_config =
+      _wire_set_rewind_configPtr.asFunction<void Function(int, int, int, int)>();
+
+  void wire_rewind_emulator(
+    int port_,
+    int id,
+    int frames,
+  ) {
+    return _wire_rewind_emulator(
+      port_,
+      id,
+      frames,
+    );
+  }
+
+  late final _wire_rewind_emulatorPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint32)>>(
+          'wire_rewind_emulator');
+  late final _wire_rewind_emulator =
+      _wire_rewind_emulatorPtr.asFunction<void Function(int, int, int)>();
+
+  void wire_set_emulation_speed(
+    int port_,
+    int id,
+    double speed,
+  ) {
+    return _wire_set_emulation_speed(
+      port_,
+      id,
+      speed,
+    );
+  }
+
+  late final _wire_set_emulation_speedPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Double)>>(
+          'wire_set_emulation_speed');
+  late final _wire_set_emulation_speed =
+      _wire_set_emulation_speedPtr.asFunction<void Function(int, int, double)>();
+
+  void wire_step_emulator_instruction(
+    int port_,
+    int id,
+  ) {
+    return _wire_step_emulator_instruction(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_step_emulator_instructionPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_step_emulator_instruction');
+  late final _wire_step_emulator_instruction =
+      _wire_step_emulator_instructionPtr.asFunction<void Function(int, int)>();
+
+  void wire_step_emulator_scanline(
+    int port_,
+    int id,
+  ) {
+    return _wire_step_emulator_scanline(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_step_emulator_scanlinePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_step_emulator_scanline');
+  late final _wire_step_emulator_scanline =
+      _wire_step_emulator_scanlinePtr.asFunction<void Function(int, int)>();
+
+  void wire_step_emulator_frame(
+    int port_,
+    int id,
+  ) {
+    return _wire_step_emulator_frame(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_step_emulator_framePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_step_emulator_frame');
+  late final _wire_step_emulator_frame =
+      _wire_step_emulator_framePtr.asFunction<void Function(int, int)>();
+
+  void wire_get_screen_buffer(
+    int port_,
+    int id,
+  ) {
+    return _wire_get_screen_buffer(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_get_screen_bufferPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_get_screen_buffer');
+  late final _wire_get_screen_buffer =
+      _wire_get_screen_bufferPtr.asFunction<void Function(int, int)>();
+
+  void wire_add_emulator_breakpoint(
+    int port_,
+    int id,
+    int address,
+    ffi.Pointer<ffi.Uint16> bank,
+    ffi.Pointer<wire_uint_8_list> condition,
+  ) {
+    return _wire_add_emulator_breakpoint(
+      port_,
+      id,
+      address,
+      bank,
+      condition,
+    );
+  }
+
+  late final _wire_add_emulator_breakpointPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint16, ffi.Pointer<ffi.Uint16>, ffi.Pointer<wire_uint_8_list>)>>(
+          'wire_add_emulator_breakpoint');
+  late final _wire_add_emulator_breakpoint =
+      _wire_add_emulator_breakpointPtr.asFunction<void Function(int, int, int, ffi.Pointer<ffi.Uint16>, ffi.Pointer<wire_uint_8_list>)>();
+
+  void wire_remove_emulator_breakpoint(
+    int port_,
+    int id,
+    int address,
+    ffi.Pointer<ffi.Uint16> bank,
+  ) {
+    return _wire_remove_emulator_breakpoint(
+      port_,
+      id,
+      address,
+      bank,
+    );
+  }
+
+  late final _wire_remove_emulator_breakpointPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint16, ffi.Pointer<ffi.Uint16>)>>(
+          'wire_remove_emulator_breakpoint');
+  late final _wire_remove_emulator_breakpoint =
+      _wire_remove_emulator_breakpointPtr.asFunction<void Function(int, int, int, ffi.Pointer<ffi.Uint16>)>();
+
+  void wire_add_emulator_watchpoint(
+    int port_,
+    int id,
+    int start,
+    int end,
+    bool read,
+    bool write,
+    bool execute,
+  ) {
+    return _wire_add_emulator_watchpoint(
+      port_,
+      id,
+      start,
+      end,
+      read,
+      write,
+      execute,
+    );
+  }
+
+  late final _wire_add_emulator_watchpointPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint16, ffi.Uint16, ffi.Bool, ffi.Bool, ffi.Bool)>>(
+          'wire_add_emulator_watchpoint');
+  late final _wire_add_emulator_watchpoint =
+      _wire_add_emulator_watchpointPtr.asFunction<void Function(int, int, int, int, bool, bool, bool)>();
+
+  void wire_remove_emulator_watchpoint(
+    int port_,
+    int id,
+    int start,
+    int end,
+  ) {
+    return _wire_remove_emulator_watchpoint(
+      port_,
+      id,
+      start,
+      end,
+    );
+  }
+
+  late final _wire_remove_emulator_watchpointPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint16, ffi.Uint16)>>(
+          'wire_remove_emulator_watchpoint');
+  late final _wire_remove_emulator_watchpoint =
+      _wire_remove_emulator_watchpointPtr.asFunction<void Function(int, int, int, int)>();
+
+  void wire_clear_emulator_breakpoints(
+    int port_,
+    int id,
+  ) {
+    return _wire_clear_emulator_breakpoints(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_clear_emulator_breakpointsPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_clear_emulator_breakpoints');
+  late final _wire_clear_emulator_breakpoints =
+      _wire_clear_emulator_breakpointsPtr.asFunction<void Function(int, int)>();
+
+  void wire_set_emulator_break_on_interrupt(
+    int port_,
+    int id,
+    bool enabled,
+  ) {
+    return _wire_set_emulator_break_on_interrupt(
+      port_,
+      id,
+      enabled,
+    );
+  }
+
+  late final _wire_set_emulator_break_on_interruptPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Bool)>>(
+          'wire_set_emulator_break_on_interrupt');
+  late final _wire_set_emulator_break_on_interrupt =
+      _wire_set_emulator_break_on_interruptPtr.asFunction<void Function(int, int, bool)>();
+
+  void wire_get_stop_reason(
+    int port_,
+    int id,
+  ) {
+    return _wire_get_stop_reason(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_get_stop_reasonPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_get_stop_reason');
+  late final _wire_get_stop_reason =
+      _wire_get_stop_reasonPtr.asFunction<void Function(int, int)>();
+
+  void wire_resume_emulator(
+    int port_,
+    int id,
+  ) {
+    return _wire_resume_emulator(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_resume_emulatorPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_resume_emulator');
+  late final _wire_resume_emulator =
+      _wire_resume_emulatorPtr.asFunction<void Function(int, int)>();
+
+  void wire_disassemble_memory(
+    int port_,
+    int id,
+    int address,
+    int count,
+  ) {
+    return _wire_disassemble_memory(
+      port_,
+      id,
+      address,
+      count,
+    );
+  }
+
+  late final _wire_disassemble_memoryPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint16, ffi.Uint32)>>(
+          'wire_disassemble_memory');
+  late final _wire_disassemble_memory =
+      _wire_disassemble_memoryPtr.asFunction<void Function(int, int, int, int)>();
+
+  void wire_start_emulator_trace_file(
+    int port_,
+    int id,
+    ffi.Pointer<wire_uint_8_list> path,
+  ) {
+    return _wire_start_emulator_trace_file(
+      port_,
+      id,
+      path,
+    );
+  }
+
+  late final _wire_start_emulator_trace_filePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Pointer<wire_uint_8_list>)>>(
+          'wire_start_emulator_trace_file');
+  late final _wire_start_emulator_trace_file =
+      _wire_start_emulator_trace_filePtr.asFunction<void Function(int, int, ffi.Pointer<wire_uint_8_list>)>();
+
+  void wire_start_emulator_trace_buffer(
+    int port_,
+    int id,
+    int capacity,
+  ) {
+    return _wire_start_emulator_trace_buffer(
+      port_,
+      id,
+      capacity,
+    );
+  }
+
+  late final _wire_start_emulator_trace_bufferPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint32)>>(
+          'wire_start_emulator_trace_buffer');
+  late final _wire_start_emulator_trace_buffer =
+      _wire_start_emulator_trace_bufferPtr.asFunction<void Function(int, int, int)>();
+
+  void wire_stop_emulator_trace(
+    int port_,
+    int id,
+  ) {
+    return _wire_stop_emulator_trace(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_stop_emulator_tracePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_stop_emulator_trace');
+  late final _wire_stop_emulator_trace =
+      _wire_stop_emulator_tracePtr.asFunction<void Function(int, int)>();
+
+  void wire_get_emulator_trace(
+    int port_,
+    int id,
+  ) {
+    return _wire_get_emulator_trace(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_get_emulator_tracePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_get_emulator_trace');
+  late final _wire_get_emulator_trace =
+      _wire_get_emulator_tracePtr.asFunction<void Function(int, int)>();
+
+  void wire_start_gdb_server(
+    int port_,
+    int id,
+    int port,
+  ) {
+    return _wire_start_gdb_server(
+      port_,
+      id,
+      port,
+    );
+  }
+
+  late final _wire_start_gdb_serverPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint16)>>(
+          'wire_start_gdb_server');
+  late final _wire_start_gdb_server =
+      _wire_start_gdb_serverPtr.asFunction<void Function(int, int, int)>();
+
+  void wire_stop_gdb_server(
+    int port_,
+  ) {
+    return _wire_stop_gdb_server(
+      port_,
+    );
+  }
+
+  late final _wire_stop_gdb_serverPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64)>>(
+          'wire_stop_gdb_server');
+  late final _wire_stop_gdb_server =
+      _wire_stop_gdb_serverPtr.asFunction<void Function(int)>();
+
+  void wire_create(
+    int port_,
+    ffi.Pointer<wire_uint_8_list> rom_data,
+    ffi.Pointer<wire_uint_8_list> ram_data,
+    ffi.Pointer<wire_uint_8_list> boot_rom,
+    int model,
+  ) {
+    return _wire_create(
+      port_,
+      rom_data,
+      ram_data,
+      boot_rom,
+      model,
+    );
+  }
+
+  late final _wire_createPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Pointer<wire_uint_8_list>, ffi.Pointer<wire_uint_8_list>, ffi.Pointer<wire_uint_8_list>, ffi.Uint8)>>(
+          'wire_create');
+  late final _wire_create =
+      _wire_createPtr.asFunction<void Function(int, ffi.Pointer<wire_uint_8_list>, ffi.Pointer<wire_uint_8_list>, ffi.Pointer<wire_uint_8_list>, int)>();
+
+  void wire_destroy(
+    int port_,
+    int id,
+  ) {
+    return _wire_destroy(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_destroyPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_destroy');
+  late final _wire_destroy =
+      _wire_destroyPtr.asFunction<void Function(int, int)>();
+
+  void wire_render(
+    int port_,
+    int id,
+  ) {
+    return _wire_render(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_renderPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_render');
+  late final _wire_render =
+      _wire_renderPtr.asFunction<void Function(int, int)>();
+
+  void wire_set_buttons(
+    int port_,
+    int id,
+    ffi.Pointer<wire_uint_8_list> button_states,
+  ) {
+    return _wire_set_buttons(
+      port_,
+      id,
+      button_states,
+    );
+  }
+
+  late final _wire_set_buttonsPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Pointer<wire_uint_8_list>)>>(
+          'wire_set_buttons');
+  late final _wire_set_buttons =
+      _wire_set_buttonsPtr.asFunction<void Function(int, int, ffi.Pointer<wire_uint_8_list>)>();
+
+  void wire_set_sample_rate(
+    int port_,
+    int id,
+    int sample_rate,
+  ) {
+    return _wire_set_sample_rate(
+      port_,
+      id,
+      sample_rate,
+    );
+  }
+
+  late final _wire_set_sample_ratePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint32)>>(
+          'wire_set_sample_rate');
+  late final _wire_set_sample_rate =
+      _wire_set_sample_ratePtr.asFunction<void Function(int, int, int)>();
+
+  void wire_drain_audio(
+    int port_,
+    int id,
+  ) {
+    return _wire_drain_audio(
+      port_,
+      id,
+    );
+  }
+
+  late final _wire_drain_audioPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_drain_audio');
+  late final _wire_drain_audio =
+      _wire_drain_audioPtr.asFunction<void Function(int, int)>();
+
+  void wire_set_cgb_audio_filter(
+    int port_,
+    int id,
+    bool enabled,
+  ) {
+    return _wire_set_cgb_audio_filter(
+      port_,
+      id,
+      enabled,
+    );
+  }
+
+  late final _wire_set_cgb_audio_filterPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Bool)>>(
+          'wire_set_cgb_audio_filter');
+  late final _wire_set_cgb_audio_filter =
+      _wire_set_cgb_audio_filterPtr.asFunction<void Function(int, int, bool)>();
 
-  Future<Uint8List?> unload({dynamic hint}) {
-    return _platform.executeNormal(FlutterRustBridgeTask(
-      callFfi: (port_) => _platform.inner.wire_unload(port_),
-      parseSuccessData: _wire2api_opt_uint_8_list,
-      parseErrorData: _wire2api_error,
-      constMeta: kUnloadConstMeta,
-      argValues: [],
-      hint: hint,
-    ));
+  void wire_mute_channel(
+    int port_,
+    int id,
+    int channel,
+    bool muted,
+  ) {
+    return _wire_mute_channel(
+      port_,
+      id,
+      channel,
+      muted,
+    );
   }
 
-  FlutterRustBridgeTaskConstMeta get kUnloadConstMeta =>
-      const FlutterRustBridgeTaskConstMeta(
-        debugName: "unload",
-        argNames: [],
-      );
+  late final _wire_mute_channelPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint8, ffi.Bool)>>(
+          'wire_mute_channel');
+  late final _wire_mute_channel =
+      _wire_mute_channelPtr.asFunction<void Function(int, int, int, bool)>();
 
-  Future<Uint32List?> render({dynamic hint}) {
-    return _platform.executeNormal(FlutterRustBridgeTask(
-      callFfi: (port_) => _platform.inner.wire_render(port_),
-      parseSuccessData: _wire2api_opt_uint_32_list,
-      parseErrorData: _wire2api_error,
-      constMeta: kRenderConstMeta,
-      argValues: [],
-      hint: hint,
-    ));
+  void wire_solo_channel(
+    int port_,
+    int id,
+    int channel,
+    bool soloed,
+  ) {
+    return _wire_solo_channel(
+      port_,
+      id,
+      channel,
+      soloed,
+    );
   }
 
-  FlutterRustBridgeTaskConstMeta get kRenderConstMeta =>
-      const FlutterRustBridgeTaskConstMeta(
-        debugName: "render",
-        argNames: [],
-      );
+  late final _wire_solo_channelPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint8, ffi.Bool)>>(
+          'wire_solo_channel');
+  late final _wire_solo_channel =
+      _wire_solo_channelPtr.asFunction<void Function(int, int, int, bool)>();
 
-  Future<void> setButtons({required Uint8List buttonStates, dynamic hint}) {
-    var arg0 = _platform.api2wire_uint_8_list(buttonStates);
-    return _platform.executeNormal(FlutterRustBridgeTask(
-      callFfi: (port_) => _platform.inner.wire_set_buttons(port_, arg0),
-      parseSuccessData: _wire2api_unit,
-      parseErrorData: _wire2api_error,
-      constMeta: kSetButtonsConstMeta,
-      argValues: [buttonStates],
-      hint: hint,
-    ));
+  void wire_channel_states(
+    int port_,
+    int id,
+  ) {
+    return _wire_channel_states(
+      port_,
+      id,
+    );
   }
 
-  FlutterRustBridgeTaskConstMeta get kSetButtonsConstMeta =>
-      const FlutterRustBridgeTaskConstMeta(
-        debugName: "set_buttons",
-        argNames: ["buttonStates"],
-      );
+  late final _wire_channel_statesPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_channel_states');
+  late final _wire_channel_states =
+      _wire_channel_statesPtr.asFunction<void Function(int, int)>();
 
-  void dispose() {
-    _platform.dispose();
+  void wire_start_recording(
+    int port_,
+    int id,
+  ) {
+    return _wire_start_recording(
+      port_,
+      id,
+    );
   }
-// Section: wire2api
 
-  Uint32List? _wire2api_opt_uint_32_list(dynamic raw) {
-    return raw == null ? null : _wire2api_uint_32_list(raw);
-  }
+  late final _wire_start_recordingPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_start_recording');
+  late final _wire_start_recording =
+      _wire_start_recordingPtr.asFunction<void Function(int, int)>();
 
-  Uint8List? _wire2api_opt_uint_8_list(dynamic raw) {
-    return raw == null ? null : _wire2api_uint_8_list(raw);
+  void wire_stop_recording(
+    int port_,
+    int id,
+  ) {
+    return _wire_stop_recording(
+      port_,
+      id,
+    );
   }
 
-  int _wire2api_u32(dynamic raw) {
-    return raw as int;
-  }
+  late final _wire_stop_recordingPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_stop_recording');
+  late final _wire_stop_recording =
+      _wire_stop_recordingPtr.asFunction<void Function(int, int)>();
 
-  int _wire2api_u8(dynamic raw) {
-    return raw as int;
+  void wire_render_audio(
+    int port_,
+    int id,
+    int sample_frames,
+    double buffer_fill,
+  ) {
+    return _wire_render_audio(
+      port_,
+      id,
+      sample_frames,
+      buffer_fill,
+    );
   }
 
-  Object _wire2api_error(dynamic raw) {
-    return raw;
+  late final _wire_render_audioPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint32, ffi.Float)>>(
+          'wire_render_audio');
+  late final _wire_render_audio =
+      _wire_render_audioPtr.asFunction<void Function(int, int, int, double)>();
+
+  void wire_save_state(
+    int port_,
+    int id,
+  ) {
+    return _wire_save_state(
+      port_,
+      id,
+    );
   }
 
-  Uint32List _wire2api_uint_32_list(dynamic raw) {
-    return raw as Uint32List;
+  late final _wire_save_statePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_save_state');
+  late final _wire_save_state =
+      _wire_save_statePtr.asFunction<void Function(int, int)>();
+
+  void wire_load_state(
+    int port_,
+    int id,
+    ffi.Pointer<wire_uint_8_list> state,
+  ) {
+    return _wire_load_state(
+      port_,
+      id,
+      state,
+    );
   }
 
-  Uint8List _wire2api_uint_8_list(dynamic raw) {
-    return raw as Uint8List;
+  late final _wire_load_statePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Pointer<wire_uint_8_list>)>>(
+          'wire_load_state');
+  late final _wire_load_state =
+      _wire_load_statePtr.asFunction<void Function(int, int, ffi.Pointer<wire_uint_8_list>)>();
+
+  void wire_configure_rewind(
+    int port_,
+    int id,
+    int memory_budget,
+    int frames_per_snapshot,
+  ) {
+    return _wire_configure_rewind(
+      port_,
+      id,
+      memory_budget,
+      frames_per_snapshot,
+    );
   }
 
-  void _wire2api_unit(dynamic raw) {
-    return;
+  late final _wire_configure_rewindPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint32, ffi.Uint32)>>(
+          'wire_configure_rewind');
+  late final _wire_configure_rewind =
+      _wire_configure_rewindPtr.asFunction<void Function(int, int, int, int)>();
+
+  void wire_rewind(
+    int port_,
+    int id,
+    int frames,
+  ) {
+    return _wire_rewind(
+      port_,
+      id,
+      frames,
+    );
   }
-}
 
-// Section: api2wire
+  late final _wire_rewindPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint32)>>(
+          'wire_rewind');
+  late final _wire_rewind =
+      _wire_rewindPtr.asFunction<void Function(int, int, int)>();
 
-@protected
-int api2wire_u8(int raw) {
-  return raw;
-}
+  void wire_set_speed(
+    int port_,
+    int id,
+    double multiplier,
+  ) {
+    return _wire_set_speed(
+      port_,
+      id,
+      multiplier,
+    );
+  }
 
-// Section: finalizer
+  late final _wire_set_speedPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Double)>>(
+          'wire_set_speed');
+  late final _wire_set_speed =
+      _wire_set_speedPtr.asFunction<void Function(int, int, double)>();
 
-class RustCorePlatform extends FlutterRustBridgeBase<RustCoreWire> {
-  RustCorePlatform(ffi.DynamicLibrary dylib) : super(RustCoreWire(dylib));
+  void wire_step_instruction(
+    int port_,
+    int id,
+  ) {
+    return _wire_step_instruction(
+      port_,
+      id,
+    );
+  }
 
-// Section: api2wire
+  late final _wire_step_instructionPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_step_instruction');
+  late final _wire_step_instruction =
+      _wire_step_instructionPtr.asFunction<void Function(int, int)>();
 
-  @protected
-  ffi.Pointer<wire_uint_8_list> api2wire_opt_uint_8_list(Uint8List? raw) {
-    return raw == null ? ffi.nullptr : api2wire_uint_8_list(raw);
+  void wire_step_scanline(
+    int port_,
+    int id,
+  ) {
+    return _wire_step_scanline(
+      port_,
+      id,
+    );
   }
 
-  @protected
-  ffi.Pointer<wire_uint_8_list> api2wire_uint_8_list(Uint8List raw) {
-    final ans = inner.new_uint_8_list_0(raw.length);
-    ans.ref.ptr.asTypedList(raw.length).setAll(0, raw);
-    return ans;
+  late final _wire_step_scanlinePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_step_scanline');
+  late final _wire_step_scanline =
+      _wire_step_scanlinePtr.asFunction<void Function(int, int)>();
+
+  void wire_step_frame(
+    int port_,
+    int id,
+  ) {
+    return _wire_step_frame(
+      port_,
+      id,
+    );
   }
-// Section: finalizer
 
-// Section: api_fill_to_wire
-}
+  late final _wire_step_framePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_step_frame');
+  late final _wire_step_frame =
+      _wire_step_framePtr.asFunction<void Function(int, int)>();
 
-// ignore_for_file: camel_case_types, non_constant_identifier_names, avoid_positional_boolean_parameters, annotate_overrides, constant_identifier_names
+  void wire_current_frame(
+    int port_,
+    int id,
+  ) {
+    return _wire_current_frame(
+      port_,
+      id,
+    );
+  }
 
-// AUTO GENERATED FILE, DO NOT EDIT.
-//
-// Generated by `package:ffigen`.
-// ignore_for_file: type=lint
+  late final _wire_current_framePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_current_frame');
+  late final _wire_current_frame =
+      _wire_current_framePtr.asFunction<void Function(int, int)>();
 
-/// generated by flutter_rust_bridge
-class RustCoreWire implements FlutterRustBridgeWireBase {
-  @internal
-  late final dartApi = DartApiDl(init_frb_dart_api_dl);
+  void wire_add_breakpoint(
+    int port_,
+    int id,
+    int address,
+    ffi.Pointer<ffi.Uint16> bank,
+    ffi.Pointer<wire_uint_8_list> condition,
+  ) {
+    return _wire_add_breakpoint(
+      port_,
+      id,
+      address,
+      bank,
+      condition,
+    );
+  }
 
-  /// Holds the symbol lookup function.
-  final ffi.Pointer<T> Function<T extends ffi.NativeType>(String symbolName)
-      _lookup;
+  late final _wire_add_breakpointPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint16, ffi.Pointer<ffi.Uint16>, ffi.Pointer<wire_uint_8_list>)>>(
+          'wire_add_breakpoint');
+  late final _wire_add_breakpoint =
+      _wire_add_breakpointPtr.asFunction<void Function(int, int, int, ffi.Pointer<ffi.Uint16>, ffi.Pointer<wire_uint_8_list>)>();
 
-  /// The symbols are looked up in [dynamicLibrary].
-  RustCoreWire(ffi.DynamicLibrary dynamicLibrary)
-      : _lookup = dynamicLibrary.lookup;
+  void wire_remove_breakpoint(
+    int port_,
+    int id,
+    int address,
+    ffi.Pointer<ffi.Uint16> bank,
+  ) {
+    return _wire_remove_breakpoint(
+      port_,
+      id,
+      address,
+      bank,
+    );
+  }
 
-  /// The symbols are looked up with [lookup].
-  RustCoreWire.fromLookup(
-      ffi.Pointer<T> Function<T extends ffi.NativeType>(String symbolName)
-          lookup)
-      : _lookup = lookup;
+  late final _wire_remove_breakpointPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint16, ffi.Pointer<ffi.Uint16>)>>(
+          'wire_remove_breakpoint');
+  late final _wire_remove_breakpoint =
+      _wire_remove_breakpointPtr.asFunction<void Function(int, int, int, ffi.Pointer<ffi.Uint16>)>();
 
-  void store_dart_post_cobject(
-    DartPostCObjectFnType ptr,
+  void wire_add_watchpoint(
+    int port_,
+    int id,
+    int start,
+    int end,
+    bool read,
+    bool write,
+    bool execute,
   ) {
-    return _store_dart_post_cobject(
-      ptr,
+    return _wire_add_watchpoint(
+      port_,
+      id,
+      start,
+      end,
+      read,
+      write,
+      execute,
     );
   }
 
-  late final _store_dart_post_cobjectPtr =
-      _lookup<ffi.NativeFunction<ffi.Void Function(DartPostCObjectFnType)>>(
-          'store_dart_post_cobject');
-  late final _store_dart_post_cobject = _store_dart_post_cobjectPtr
-      .asFunction<void Function(DartPostCObjectFnType)>();
+  late final _wire_add_watchpointPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint16, ffi.Uint16, ffi.Bool, ffi.Bool, ffi.Bool)>>(
+          'wire_add_watchpoint');
+  late final _wire_add_watchpoint =
+      _wire_add_watchpointPtr.asFunction<void Function(int, int, int, int, bool, bool, bool)>();
 
-  Object get_dart_object(
-    int ptr,
+  void wire_remove_watchpoint(
+    int port_,
+    int id,
+    int start,
+    int end,
   ) {
-    return _get_dart_object(
-      ptr,
+    return _wire_remove_watchpoint(
+      port_,
+      id,
+      start,
+      end,
     );
   }
 
-  late final _get_dart_objectPtr =
-      _lookup<ffi.NativeFunction<ffi.Handle Function(ffi.UintPtr)>>(
-          'get_dart_object');
-  late final _get_dart_object =
-      _get_dart_objectPtr.asFunction<Object Function(int)>();
+  late final _wire_remove_watchpointPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint16, ffi.Uint16)>>(
+          'wire_remove_watchpoint');
+  late final _wire_remove_watchpoint =
+      _wire_remove_watchpointPtr.asFunction<void Function(int, int, int, int)>();
 
-  void drop_dart_object(
-    int ptr,
+  void wire_clear_breakpoints(
+    int port_,
+    int id,
   ) {
-    return _drop_dart_object(
-      ptr,
+    return _wire_clear_breakpoints(
+      port_,
+      id,
     );
   }
 
-  late final _drop_dart_objectPtr =
-      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.UintPtr)>>(
-          'drop_dart_object');
-  late final _drop_dart_object =
-      _drop_dart_objectPtr.asFunction<void Function(int)>();
+  late final _wire_clear_breakpointsPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_clear_breakpoints');
+  late final _wire_clear_breakpoints =
+      _wire_clear_breakpointsPtr.asFunction<void Function(int, int)>();
 
-  int new_dart_opaque(
-    Object handle,
+  void wire_break_on_interrupt(
+    int port_,
+    int id,
+    bool enabled,
   ) {
-    return _new_dart_opaque(
-      handle,
+    return _wire_break_on_interrupt(
+      port_,
+      id,
+      enabled,
     );
   }
 
-  late final _new_dart_opaquePtr =
-      _lookup<ffi.NativeFunction<ffi.UintPtr Function(ffi.Handle)>>(
-          'new_dart_opaque');
-  late final _new_dart_opaque =
-      _new_dart_opaquePtr.asFunction<int Function(Object)>();
+  late final _wire_break_on_interruptPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Bool)>>(
+          'wire_break_on_interrupt');
+  late final _wire_break_on_interrupt =
+      _wire_break_on_interruptPtr.asFunction<void Function(int, int, bool)>();
 
-  int init_frb_dart_api_dl(
-    ffi.Pointer<ffi.Void> obj,
+  void wire_stop_reason(
+    int port_,
+    int id,
   ) {
-    return _init_frb_dart_api_dl(
-      obj,
+    return _wire_stop_reason(
+      port_,
+      id,
     );
   }
 
-  late final _init_frb_dart_api_dlPtr =
-      _lookup<ffi.NativeFunction<ffi.IntPtr Function(ffi.Pointer<ffi.Void>)>>(
-          'init_frb_dart_api_dl');
-  late final _init_frb_dart_api_dl = _init_frb_dart_api_dlPtr
-      .asFunction<int Function(ffi.Pointer<ffi.Void>)>();
+  late final _wire_stop_reasonPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_stop_reason');
+  late final _wire_stop_reason =
+      _wire_stop_reasonPtr.asFunction<void Function(int, int)>();
 
-  void wire_load_rom(
+  void wire_resume(
     int port_,
-    ffi.Pointer<wire_uint_8_list> rom_data,
-    ffi.Pointer<wire_uint_8_list> ram_data,
+    int id,
   ) {
-    return _wire_load_rom(
+    return _wire_resume(
       port_,
-      rom_data,
-      ram_data,
+      id,
     );
   }
 
-  late final _wire_load_romPtr = _lookup<
-      ffi.NativeFunction<
-          ffi.Void Function(ffi.Int64, ffi.Pointer<wire_uint_8_list>,
-              ffi.Pointer<wire_uint_8_list>)>>('wire_load_rom');
-  late final _wire_load_rom = _wire_load_romPtr.asFunction<
-      void Function(
-          int, ffi.Pointer<wire_uint_8_list>, ffi.Pointer<wire_uint_8_list>)>();
+  late final _wire_resumePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_resume');
+  late final _wire_resume =
+      _wire_resumePtr.asFunction<void Function(int, int)>();
 
-  void wire_unload_emulator(
+  void wire_disassemble(
     int port_,
+    int id,
+    int address,
+    int count,
   ) {
-    return _wire_unload_emulator(
+    return _wire_disassemble(
       port_,
+      id,
+      address,
+      count,
     );
   }
 
-  late final _wire_unload_emulatorPtr =
-      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64)>>(
-          'wire_unload_emulator');
-  late final _wire_unload_emulator =
-      _wire_unload_emulatorPtr.asFunction<void Function(int)>();
+  late final _wire_disassemblePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint16, ffi.Uint32)>>(
+          'wire_disassemble');
+  late final _wire_disassemble =
+      _wire_disassemblePtr.asFunction<void Function(int, int, int, int)>();
 
-  void wire_render_frame(
+  void wire_trace_to_file(
     int port_,
+    int id,
+    ffi.Pointer<wire_uint_8_list> path,
   ) {
-    return _wire_render_frame(
+    return _wire_trace_to_file(
       port_,
+      id,
+      path,
     );
   }
 
-  late final _wire_render_framePtr =
-      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64)>>(
-          'wire_render_frame');
-  late final _wire_render_frame =
-      _wire_render_framePtr.asFunction<void Function(int)>();
+  late final _wire_trace_to_filePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Pointer<wire_uint_8_list>)>>(
+          'wire_trace_to_file');
+  late final _wire_trace_to_file =
+      _wire_trace_to_filePtr.asFunction<void Function(int, int, ffi.Pointer<wire_uint_8_list>)>();
 
-  void wire_set_buttons_state(
+  void wire_trace_to_buffer(
     int port_,
-    ffi.Pointer<wire_uint_8_list> button_states,
+    int id,
+    int lines,
   ) {
-    return _wire_set_buttons_state(
+    return _wire_trace_to_buffer(
       port_,
-      button_states,
+      id,
+      lines,
     );
   }
 
-  late final _wire_set_buttons_statePtr = _lookup<
-      ffi.NativeFunction<
-          ffi.Void Function(ffi.Int64,
-              ffi.Pointer<wire_uint_8_list>)>>('wire_set_buttons_state');
-  late final _wire_set_buttons_state = _wire_set_buttons_statePtr
-      .asFunction<void Function(int, ffi.Pointer<wire_uint_8_list>)>();
+  late final _wire_trace_to_bufferPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint32)>>(
+          'wire_trace_to_buffer');
+  late final _wire_trace_to_buffer =
+      _wire_trace_to_bufferPtr.asFunction<void Function(int, int, int)>();
 
-  void wire_load(
+  void wire_stop_trace(
     int port_,
-    ffi.Pointer<wire_uint_8_list> rom_data,
-    ffi.Pointer<wire_uint_8_list> ram_data,
+    int id,
   ) {
-    return _wire_load(
+    return _wire_stop_trace(
       port_,
-      rom_data,
-      ram_data,
+      id,
     );
   }
 
-  late final _wire_loadPtr = _lookup<
-      ffi.NativeFunction<
-          ffi.Void Function(ffi.Int64, ffi.Pointer<wire_uint_8_list>,
-              ffi.Pointer<wire_uint_8_list>)>>('wire_load');
-  late final _wire_load = _wire_loadPtr.asFunction<
-      void Function(
-          int, ffi.Pointer<wire_uint_8_list>, ffi.Pointer<wire_uint_8_list>)>();
+  late final _wire_stop_tracePtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_stop_trace');
+  late final _wire_stop_trace =
+      _wire_stop_tracePtr.asFunction<void Function(int, int)>();
 
-  void wire_unload(
+  void wire_trace_log(
     int port_,
+    int id,
   ) {
-    return _wire_unload(
+    return _wire_trace_log(
       port_,
+      id,
     );
   }
 
-  late final _wire_unloadPtr =
-      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64)>>('wire_unload');
-  late final _wire_unload = _wire_unloadPtr.asFunction<void Function(int)>();
+  late final _wire_trace_logPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32)>>(
+          'wire_trace_log');
+  late final _wire_trace_log =
+      _wire_trace_logPtr.asFunction<void Function(int, int)>();
 
-  void wire_render(
+  void wire_start_gdb(
     int port_,
+    int id,
+    int port,
   ) {
-    return _wire_render(
+    return _wire_start_gdb(
       port_,
+      id,
+      port,
     );
   }
 
-  late final _wire_renderPtr =
-      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64)>>('wire_render');
-  late final _wire_render = _wire_renderPtr.asFunction<void Function(int)>();
+  late final _wire_start_gdbPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Uint32, ffi.Uint16)>>(
+          'wire_start_gdb');
+  late final _wire_start_gdb =
+      _wire_start_gdbPtr.asFunction<void Function(int, int, int)>();
 
-  void wire_set_buttons(
+  void wire_stop_gdb(
     int port_,
-    ffi.Pointer<wire_uint_8_list> button_states,
   ) {
-    return _wire_set_buttons(
+    return _wire_stop_gdb(
       port_,
-      button_states,
     );
   }
 
-  late final _wire_set_buttonsPtr = _lookup<
-      ffi.NativeFunction<
-          ffi.Void Function(
-              ffi.Int64, ffi.Pointer<wire_uint_8_list>)>>('wire_set_buttons');
-  late final _wire_set_buttons = _wire_set_buttonsPtr
-      .asFunction<void Function(int, ffi.Pointer<wire_uint_8_list>)>();
+  late final _wire_stop_gdbPtr =
+      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64)>>(
+          'wire_stop_gdb');
+  late final _wire_stop_gdb =
+      _wire_stop_gdbPtr.asFunction<void Function(int)>();
 
   ffi.Pointer<wire_uint_8_list> new_uint_8_list_0(
     int len,
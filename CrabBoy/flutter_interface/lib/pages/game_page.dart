@@ -8,6 +8,7 @@ import 'package:flutter_interface/components/game_screen.dart';
 import 'package:flutter_interface/components/speed_toggle_button.dart';
 import 'package:flutter_interface/services/rust_core_service.dart';
 import 'package:flutter_interface/components/action_button.dart';
+import 'package:flutter_rust_bridge/flutter_rust_bridge.dart';
 import 'package:path_provider/path_provider.dart';
 
 class GamePage extends StatefulWidget {
@@ -28,6 +29,7 @@ class _GamePageState extends State<GamePage> {
   Uint32List _frameBuffer = Uint32List(160 * 144);
   bool _isRunning = false;
   bool _isLoaded = false;
+  int? _emulatorId;
   bool _isDoubleSpeed = false;
   Duration frameDuration = Duration(milliseconds: 16);
 
@@ -50,15 +52,47 @@ class _GamePageState extends State<GamePage> {
 
   Future<void> _loadGame() async {
     try {
-      await api.load(romData: widget.romData, ramData: widget.ramData);
+      final id = await api.create(
+          romData: widget.romData, ramData: widget.ramData, model: 0);
       setState(() {
+        _emulatorId = id;
         _isLoaded = true;
       });
     } catch (e) {
       print("Błąd podczas ładowania ROM: $e");
+      _showError(e);
     }
   }
 
+  // Tells the player why the game can't run and leaves the game page once they confirm.
+  void _showError(Object error) {
+    if (!mounted) return;
+    // The core reports a readable message on the first line, any further lines are debug details.
+    final message =
+        error is FrbAnyhowException ? error.anyhow.split('\n').first : '$error';
+    showDialog(
+      context: context,
+      builder: (BuildContext context) {
+        return AlertDialog(
+          title: Text('Cannot run this game'),
+          content: Text(message),
+          actions: [
+            TextButton(
+              onPressed: () {
+                Navigator.of(context).pop();
+              },
+              child: Text('OK'),
+            ),
+          ],
+        );
+      },
+    ).then((_) {
+      if (mounted) {
+        Navigator.of(context).pop();
+      }
+    });
+  }
+
   Future<void> _saveGameRam(String gameName, Uint8List ramData) async {
     final storagePath = await _getRomStoragePath();
     final ramFilePath = '$storagePath/$gameName.sav';
@@ -87,21 +121,26 @@ class _GamePageState extends State<GamePage> {
   void _startGameLoop() {
     _timer?.cancel();
     _timer = Timer.periodic(frameDuration, (timer) async {
-      if (_isRunning) {
+      final id = _emulatorId;
+      if (_isRunning && id != null) {
         try {
           Uint8List buttonStates = _getButtonStates();
 
-          final frame = await api.render();
+          final output = await api.render(id: id);
 
-          await api.setButtons(buttonStates: buttonStates);
+          await api.setButtons(id: id, buttonStates: buttonStates);
 
-          if (frame != null) {
-            setState(() {
-              _frameBuffer = Uint32List.fromList(frame);
-            });
-          }
+          setState(() {
+            _frameBuffer = Uint32List.fromList(output.frame);
+          });
         } catch (e) {
           print('Błąd podczas renderowania klatki: $e');
+          if (!_isRunning) return; // An earlier frame already reported it
+          timer.cancel();
+          setState(() {
+            _isRunning = false;
+          });
+          _showError(e);
         }
       }
     });
@@ -198,9 +237,13 @@ class _GamePageState extends State<GamePage> {
             child: IconButton(
               icon: Icon(Icons.arrow_back, size: 30, color: Colors.white),
               onPressed: () async {
-                final ramData = await api.unload();
-                if (ramData != null) {
-                  await _saveGameRam(gameName, ramData);
+                final id = _emulatorId;
+                if (id != null) {
+                  _timer?.cancel();
+                  final ramData = await api.destroy(id: id);
+                  if (ramData != null) {
+                    await _saveGameRam(gameName, ramData);
+                  }
                 }
                 Navigator.of(context).pop();
               },
@@ -1,47 +1,144 @@
+use anyhow::Result;
 use lazy_static::lazy_static;
-use std::sync::Mutex;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
 use flutter_rust_bridge::frb;
 
+use crate::apu::{ChannelState, HighPassModel};
+use crate::bootrom::Model;
 use crate::cartridge::CARTRIDGE;
-use crate::emulator::EMULATOR;
+use crate::cpu::disasm::Instruction;
+use crate::debugger::{StopKind, StopReason, Watchpoint};
+use crate::emulator::{AudioSyncOutput, FrameOutput, MachineState, EMULATOR};
+use crate::error::CoreError;
+use crate::gdbstub::GdbServer;
+
+#[frb(mirror(ChannelState))]
+pub struct _ChannelState {
+    pub enabled: bool,
+    pub frequency_hz: f64,
+    pub volume: u8,
+    pub duty: u8,
+    pub length_remaining: u16,
+}
+
+#[frb(mirror(FrameOutput))]
+pub struct _FrameOutput {
+    pub frame: Vec<u32>,
+    pub stop_reason: Option<StopReason>,
+}
+
+#[frb(mirror(AudioSyncOutput))]
+pub struct _AudioSyncOutput {
+    pub frame: Vec<u32>,
+    pub samples: Vec<i16>,
+    pub stop_reason: Option<StopReason>,
+}
+
+#[frb(mirror(MachineState))]
+pub struct _MachineState {
+    pub a: u8,
+    pub b: u8,
+    pub c: u8,
+    pub d: u8,
+    pub e: u8,
+    pub f: u8,
+    pub h: u8,
+    pub l: u8,
+    pub pc: u16,
+    pub sp: u16,
+    pub ime: bool,
+    pub halted: bool,
+    pub ly: u8,
+    pub ppu_mode: u8,
+}
+
+#[frb(mirror(StopKind))]
+pub enum _StopKind {
+    Breakpoint,
+    ReadWatchpoint,
+    WriteWatchpoint,
+    ExecuteWatchpoint,
+    Interrupt,
+}
+
+#[frb(mirror(StopReason))]
+pub struct _StopReason {
+    pub kind: StopKind,
+    pub address: u16,
+}
+
+#[frb(mirror(Instruction))]
+pub struct _Instruction {
+    pub address: u16,
+    pub bytes: Vec<u8>,
+    pub text: String,
+    pub length: u8,
+    pub cycles: u8,
+    pub cycles_taken: u8,
+}
 
 lazy_static! {
-    static ref EMULATOR_INSTANCE: Mutex<Option<EMULATOR>> = Mutex::new(None);
+    static ref EMULATOR_INSTANCES: Mutex<Instances> = Mutex::new(Instances::default());
+    static ref GDB_SERVER: Mutex<Option<(u32, GdbServer)>> = Mutex::new(None); // Server and the instance it debugs
 }
 
-pub fn load_rom(rom_data: Vec<u8>, ram_data: Option<Vec<u8>>) {
-    let cartridge = CARTRIDGE::new(rom_data, ram_data);
-    let emulator = EMULATOR::new(cartridge);
+#[derive(Default)]
+struct Instances {
+    emulators: HashMap<u32, Arc<Mutex<EMULATOR>>>,
+    last_id: u32, // Ids are never reused, so a stale id can't reach a newer instance
+}
 
-    let mut emulator_instance = EMULATOR_INSTANCE.lock().unwrap();
-    *emulator_instance = Some(emulator);
+// Runs the action on an instance. The registry is not held meanwhile, so separate instances run in parallel.
+fn with_emulator<R>(id: u32, action: impl FnOnce(&mut EMULATOR) -> R) -> Option<R> {
+    let emulator = EMULATOR_INSTANCES.lock().unwrap().emulators.get(&id).cloned()?;
+    let mut emulator = emulator.lock().unwrap();
+    Some(action(&mut emulator))
 }
 
-pub fn unload_emulator() -> Option<Vec<u8>> {
-    let mut emulator_instance = EMULATOR_INSTANCE.lock().unwrap();
-    
-    let ram_data = emulator_instance.as_ref().and_then(|emulator| emulator.save_ram());
-    
-    *emulator_instance = None;
-    ram_data
+// 0 selects the DMG, 1 the MGB (Game Boy Pocket) and 2 the CGB.
+fn hardware_model(model: u8) -> Model {
+    match model {
+        1 => Model::Mgb,
+        2 => Model::Cgb,
+        _ => Model::Dmg,
+    }
+}
+
+// A boot ROM image runs before the cartridge when supplied, 256 bytes for the DMG and MGB or 2304 bytes for
+// the CGB. Returns the id of the new instance, or a CoreError describing why the images can't be run.
+pub fn create_emulator(rom_data: Vec<u8>, ram_data: Option<Vec<u8>>, boot_rom: Option<Vec<u8>>, model: u8) -> Result<u32> {
+    let cartridge = CARTRIDGE::new(rom_data, ram_data)?;
+    let emulator = EMULATOR::new(cartridge, hardware_model(model), boot_rom)?;
+
+    let mut instances = EMULATOR_INSTANCES.lock().unwrap();
+    instances.last_id += 1;
+    let id = instances.last_id;
+    instances.emulators.insert(id, Arc::new(Mutex::new(emulator)));
+    Ok(id)
 }
 
-pub fn render_frame() -> Option<Vec<u32>> {
-    let mut emulator_instance = EMULATOR_INSTANCE.lock().unwrap();
-    
-    if let Some(ref mut emulator) = *emulator_instance {
-        Some(emulator.render_frame())
-    } else {
-        None
+// Returns the cartridge RAM of the destroyed instance, so it can be saved.
+pub fn destroy_emulator(id: u32) -> Option<Vec<u8>> {
+    let emulator = EMULATOR_INSTANCES.lock().unwrap().emulators.remove(&id)?;
+    let ram_data = emulator.lock().unwrap().save_ram();
+
+    let mut gdb_server = GDB_SERVER.lock().unwrap();
+    if matches!(*gdb_server, Some((debugged, _)) if debugged == id) {
+        *gdb_server = None;
     }
+    ram_data
+}
+
+// Holds on the current frame while a breakpoint or watchpoint has stopped execution, see resume_emulator.
+pub fn render_frame(id: u32) -> Result<FrameOutput> {
+    Ok(with_emulator(id, |emulator| emulator.render_frame()).ok_or(CoreError::UnknownInstance(id))?)
 }
 
-pub fn set_buttons_state(button_states: Vec<u8>) {
-    let mut emulator_instance = EMULATOR_INSTANCE.lock().unwrap();
-    
-    if let Some(ref mut emulator) = *emulator_instance {
-        if button_states.len() == 8 {
+pub fn set_buttons_state(id: u32, button_states: Vec<u8>) {
+    if button_states.len() == 8 {
+        with_emulator(id, |emulator| {
             emulator.set_button_states(
                 button_states[0], // Up
                 button_states[1], // Down
@@ -51,27 +148,341 @@ pub fn set_buttons_state(button_states: Vec<u8>) {
                 button_states[5], // B
                 button_states[6], // Start
                 button_states[7], // Select
-            );
-        }
+            )
+        });
+    }
+}
+
+pub fn set_audio_sample_rate(id: u32, sample_rate: u32) {
+    with_emulator(id, |emulator| emulator.set_audio_sample_rate(sample_rate));
+}
+
+pub fn drain_audio_samples(id: u32) -> Option<Vec<i16>> {
+    with_emulator(id, |emulator| emulator.take_audio_samples())
+}
+
+pub fn set_audio_filter_model(id: u32, cgb: bool) {
+    let model = if cgb { HighPassModel::Cgb } else { HighPassModel::Dmg };
+    with_emulator(id, |emulator| emulator.set_audio_high_pass_model(model));
+}
+
+pub fn set_audio_channel_muted(id: u32, channel: u8, muted: bool) {
+    with_emulator(id, |emulator| emulator.set_audio_channel_muted(channel as usize, muted));
+}
+
+pub fn set_audio_channel_soloed(id: u32, channel: u8, soloed: bool) {
+    with_emulator(id, |emulator| emulator.set_audio_channel_soloed(channel as usize, soloed));
+}
+
+pub fn get_audio_channel_states(id: u32) -> Option<Vec<ChannelState>> {
+    with_emulator(id, |emulator| emulator.audio_channel_states())
+}
+
+pub fn start_audio_recording(id: u32) {
+    with_emulator(id, |emulator| emulator.start_audio_recording());
+}
+
+pub fn stop_audio_recording(id: u32) -> Option<Vec<u8>> {
+    with_emulator(id, |emulator| emulator.stop_audio_recording()).flatten()
+}
+
+pub fn run_until_audio_samples(id: u32, sample_frames: u32, buffer_fill: f32) -> Option<AudioSyncOutput> {
+    with_emulator(id, |emulator| emulator.run_until_audio(sample_frames as usize, buffer_fill))
+}
+
+pub fn save_emulator_state(id: u32) -> Option<Vec<u8>> {
+    with_emulator(id, |emulator| emulator.save_state())
+}
+
+pub fn load_emulator_state(id: u32, state: Vec<u8>) -> Result<()> {
+    Ok(with_emulator(id, |emulator| emulator.load_state(&state)).ok_or(CoreError::UnknownInstance(id))??)
+}
+
+pub fn set_rewind_config(id: u32, memory_budget: u32, frames_per_snapshot: u32) {
+    with_emulator(id, |emulator| emulator.configure_rewind(memory_budget as usize, frames_per_snapshot));
+}
+
+pub fn rewind_emulator(id: u32, frames: u32) -> u32 {
+    with_emulator(id, |emulator| emulator.rewind(frames)).unwrap_or(0)
+}
+
+pub fn set_emulation_speed(id: u32, speed: f64) {
+    with_emulator(id, |emulator| emulator.set_speed(speed));
+}
+
+pub fn step_emulator_instruction(id: u32) -> Option<MachineState> {
+    with_emulator(id, |emulator| emulator.step_instruction())
+}
+
+pub fn step_emulator_scanline(id: u32) -> Option<MachineState> {
+    with_emulator(id, |emulator| emulator.step_scanline())
+}
+
+pub fn step_emulator_frame(id: u32) -> Option<MachineState> {
+    with_emulator(id, |emulator| emulator.step_frame())
+}
+
+pub fn get_screen_buffer(id: u32) -> Option<Vec<u32>> {
+    with_emulator(id, |emulator| emulator.screen_buffer())
+}
+
+pub fn add_emulator_breakpoint(id: u32, address: u16, bank: Option<u16>, condition: Option<String>) -> bool {
+    with_emulator(id, |emulator| emulator.add_breakpoint(address, bank, condition.as_deref())).unwrap_or(false)
+}
+
+pub fn remove_emulator_breakpoint(id: u32, address: u16, bank: Option<u16>) {
+    with_emulator(id, |emulator| emulator.remove_breakpoint(address, bank));
+}
+
+pub fn add_emulator_watchpoint(id: u32, start: u16, end: u16, read: bool, write: bool, execute: bool) {
+    with_emulator(id, |emulator| emulator.add_watchpoint(Watchpoint { start, end, read, write, execute }));
+}
+
+pub fn remove_emulator_watchpoint(id: u32, start: u16, end: u16) {
+    with_emulator(id, |emulator| emulator.remove_watchpoint(start, end));
+}
+
+pub fn clear_emulator_breakpoints(id: u32) {
+    with_emulator(id, |emulator| emulator.clear_breakpoints());
+}
+
+pub fn set_emulator_break_on_interrupt(id: u32, enabled: bool) {
+    with_emulator(id, |emulator| emulator.set_break_on_interrupt(enabled));
+}
+
+pub fn get_stop_reason(id: u32) -> Option<StopReason> {
+    with_emulator(id, |emulator| emulator.stop_reason()).flatten()
+}
+
+// Continues from a breakpoint or watchpoint, the instruction execution stopped on runs first.
+pub fn resume_emulator(id: u32) {
+    with_emulator(id, |emulator| emulator.resume());
+}
+
+pub fn disassemble_memory(id: u32, address: u16, count: u32) -> Option<Vec<Instruction>> {
+    with_emulator(id, |emulator| emulator.disassemble(address, count as usize))
+}
+
+pub fn start_emulator_trace_file(id: u32, path: String) -> bool {
+    with_emulator(id, |emulator| emulator.start_trace_file(&path)).unwrap_or(false)
+}
+
+pub fn start_emulator_trace_buffer(id: u32, capacity: u32) {
+    with_emulator(id, |emulator| emulator.start_trace_buffer(capacity as usize));
+}
+
+pub fn stop_emulator_trace(id: u32) {
+    with_emulator(id, |emulator| emulator.stop_trace());
+}
+
+pub fn get_emulator_trace(id: u32) -> Option<String> {
+    with_emulator(id, |emulator| emulator.trace_lines().join("\n"))
+}
+
+// Only one instance can be debugged at a time, starting a server for another one stops the previous server.
+pub fn start_gdb_server(id: u32, port: u16) -> bool {
+    let mut gdb_server = GDB_SERVER.lock().unwrap();
+
+    // Stops the previous server first, so it releases its port.
+    *gdb_server = None;
+    let emulator = match EMULATOR_INSTANCES.lock().unwrap().emulators.get(&id) {
+        Some(emulator) => Arc::downgrade(emulator),
+        None => return false,
+    };
+    match GdbServer::start(port, emulator) {
+        Ok(server) => {
+            *gdb_server = Some((id, server));
+            true
+        },
+        Err(_) => false,
     }
 }
 
+pub fn stop_gdb_server() {
+    *GDB_SERVER.lock().unwrap() = None;
+}
+
+#[frb]
+pub fn create(rom_data: Vec<u8>, ram_data: Option<Vec<u8>>, boot_rom: Option<Vec<u8>>, model: u8) -> Result<u32> {
+    create_emulator(rom_data, ram_data, boot_rom, model)
+}
+
+#[frb]
+pub fn destroy(id: u32) -> Option<Vec<u8>> {
+    destroy_emulator(id)
+}
+
+#[frb]
+pub fn render(id: u32) -> Result<FrameOutput> {
+    render_frame(id)
+}
+
+#[frb]
+pub fn set_buttons(id: u32, button_states: Vec<u8>) {
+    set_buttons_state(id, button_states);
+}
+
+#[frb]
+pub fn set_sample_rate(id: u32, sample_rate: u32) {
+    set_audio_sample_rate(id, sample_rate);
+}
+
+#[frb]
+pub fn drain_audio(id: u32) -> Option<Vec<i16>> {
+    drain_audio_samples(id)
+}
+
+#[frb]
+pub fn set_cgb_audio_filter(id: u32, enabled: bool) {
+    set_audio_filter_model(id, enabled);
+}
+
+#[frb]
+pub fn mute_channel(id: u32, channel: u8, muted: bool) {
+    set_audio_channel_muted(id, channel, muted);
+}
+
+#[frb]
+pub fn solo_channel(id: u32, channel: u8, soloed: bool) {
+    set_audio_channel_soloed(id, channel, soloed);
+}
+
+#[frb]
+pub fn channel_states(id: u32) -> Option<Vec<ChannelState>> {
+    get_audio_channel_states(id)
+}
+
+#[frb]
+pub fn start_recording(id: u32) {
+    start_audio_recording(id);
+}
+
+#[frb]
+pub fn stop_recording(id: u32) -> Option<Vec<u8>> {
+    stop_audio_recording(id)
+}
+
+#[frb]
+pub fn render_audio(id: u32, sample_frames: u32, buffer_fill: f32) -> Option<AudioSyncOutput> {
+    run_until_audio_samples(id, sample_frames, buffer_fill)
+}
+
+#[frb]
+pub fn save_state(id: u32) -> Option<Vec<u8>> {
+    save_emulator_state(id)
+}
+
+#[frb]
+pub fn load_state(id: u32, state: Vec<u8>) -> Result<()> {
+    load_emulator_state(id, state)
+}
+
+#[frb]
+pub fn configure_rewind(id: u32, memory_budget: u32, frames_per_snapshot: u32) {
+    set_rewind_config(id, memory_budget, frames_per_snapshot)
+}
+
+#[frb]
+pub fn rewind(id: u32, frames: u32) -> u32 {
+    rewind_emulator(id, frames)
+}
+
+#[frb]
+pub fn set_speed(id: u32, multiplier: f64) {
+    set_emulation_speed(id, multiplier)
+}
+
+#[frb]
+pub fn step_instruction(id: u32) -> Option<MachineState> {
+    step_emulator_instruction(id)
+}
+
+#[frb]
+pub fn step_scanline(id: u32) -> Option<MachineState> {
+    step_emulator_scanline(id)
+}
+
+#[frb]
+pub fn step_frame(id: u32) -> Option<MachineState> {
+    step_emulator_frame(id)
+}
+
+#[frb]
+pub fn current_frame(id: u32) -> Option<Vec<u32>> {
+    get_screen_buffer(id)
+}
+
+#[frb]
+pub fn add_breakpoint(id: u32, address: u16, bank: Option<u16>, condition: Option<String>) -> bool {
+    add_emulator_breakpoint(id, address, bank, condition)
+}
+
+#[frb]
+pub fn remove_breakpoint(id: u32, address: u16, bank: Option<u16>) {
+    remove_emulator_breakpoint(id, address, bank)
+}
+
+#[frb]
+pub fn add_watchpoint(id: u32, start: u16, end: u16, read: bool, write: bool, execute: bool) {
+    add_emulator_watchpoint(id, start, end, read, write, execute)
+}
+
+#[frb]
+pub fn remove_watchpoint(id: u32, start: u16, end: u16) {
+    remove_emulator_watchpoint(id, start, end)
+}
+
+#[frb]
+pub fn clear_breakpoints(id: u32) {
+    clear_emulator_breakpoints(id)
+}
+
+#[frb]
+pub fn break_on_interrupt(id: u32, enabled: bool) {
+    set_emulator_break_on_interrupt(id, enabled)
+}
+
+#[frb]
+pub fn stop_reason(id: u32) -> Option<StopReason> {
+    get_stop_reason(id)
+}
+
+#[frb]
+pub fn resume(id: u32) {
+    resume_emulator(id);
+}
+
+#[frb]
+pub fn disassemble(id: u32, address: u16, count: u32) -> Option<Vec<Instruction>> {
+    disassemble_memory(id, address, count)
+}
+
+#[frb]
+pub fn trace_to_file(id: u32, path: String) -> bool {
+    start_emulator_trace_file(id, path)
+}
+
+#[frb]
+pub fn trace_to_buffer(id: u32, lines: u32) {
+    start_emulator_trace_buffer(id, lines);
+}
+
 #[frb]
-pub fn load(rom_data: Vec<u8>, ram_data: Option<Vec<u8>>) {
-    load_rom(rom_data, ram_data);
+pub fn stop_trace(id: u32) {
+    stop_emulator_trace(id);
 }
 
 #[frb]
-pub fn unload() -> Option<Vec<u8>> {
-    unload_emulator()
+pub fn trace_log(id: u32) -> Option<String> {
+    get_emulator_trace(id)
 }
 
 #[frb]
-pub fn render() -> Option<Vec<u32>> {
-    render_frame()
+pub fn start_gdb(id: u32, port: u16) -> bool {
+    start_gdb_server(id, port)
 }
 
 #[frb]
-pub fn set_buttons(button_states: Vec<u8>) {
-    set_buttons_state(button_states);
+pub fn stop_gdb() {
+    stop_gdb_server();
 }
@@ -0,0 +1,91 @@
+use std::f64::consts::PI;
+
+const PHASE_COUNT: usize = 32;  // Sub-sample positions a step can start at
+const KERNEL_WIDTH: usize = 16; // Output samples touched by a single step
+const CUTOFF: f64 = 0.9;        // Fraction of the Nyquist frequency kept by the kernel
+
+// Band-limited step synthesis: instead of sampling the channel output directly, every change in
+// amplitude is recorded as a delta smeared over a few output samples by a windowed sinc kernel.
+// Integrating the deltas gives a step without the aliasing a plain decimation would produce.
+pub struct BlipBuffer {
+    samples_per_clock: f64,
+    offset: f64,       // Position of the current clock origin, in output samples
+    deltas: Vec<f32>,
+    integrator: f32,
+    kernel: [[f32; KERNEL_WIDTH]; PHASE_COUNT],
+}
+
+impl BlipBuffer {
+    pub fn new(clock_rate: u32, sample_rate: u32) -> Self {
+        BlipBuffer {
+            samples_per_clock: sample_rate as f64 / clock_rate as f64,
+            offset: 0.0,
+            deltas: Vec::new(),
+            integrator: 0.0,
+            kernel: build_kernel(),
+        }
+    }
+
+    // Must only be called right after the buffer was read, while no deltas are pending.
+    pub fn set_rates(&mut self, clock_rate: u32, sample_rate: f64) {
+        self.samples_per_clock = sample_rate / clock_rate as f64;
+    }
+
+    pub fn add_delta(&mut self, clock_time: u32, delta: f32) {
+        let position = self.offset + clock_time as f64 * self.samples_per_clock;
+        let index = position as usize;
+        let phase = ((position - index as f64) * PHASE_COUNT as f64) as usize;
+
+        if self.deltas.len() < index + KERNEL_WIDTH {
+            self.deltas.resize(index + KERNEL_WIDTH, 0.0);
+        }
+
+        for (tap, weight) in self.kernel[phase].iter().enumerate() {
+            self.deltas[index + tap] += delta * weight;
+        }
+    }
+
+    pub fn end_frame(&mut self, clock_duration: u32) {
+        self.offset += clock_duration as f64 * self.samples_per_clock;
+    }
+
+    pub fn read_samples(&mut self, output: &mut Vec<f32>) {
+        let count = self.offset as usize;
+        if self.deltas.len() < count {
+            self.deltas.resize(count, 0.0);
+        }
+
+        for delta in self.deltas.drain(..count) {
+            self.integrator += delta;
+            output.push(self.integrator);
+        }
+        self.offset -= count as f64;
+    }
+}
+
+fn build_kernel() -> [[f32; KERNEL_WIDTH]; PHASE_COUNT] {
+    let mut kernel = [[0.0; KERNEL_WIDTH]; PHASE_COUNT];
+    let half_width = KERNEL_WIDTH as f64 / 2.0;
+
+    for (phase, taps) in kernel.iter_mut().enumerate() {
+        let center = half_width - 1.0 + phase as f64 / PHASE_COUNT as f64;
+
+        let mut weights = [0.0f64; KERNEL_WIDTH];
+        for (tap, weight) in weights.iter_mut().enumerate() {
+            let t = tap as f64 - center;
+            let sinc = if t == 0.0 { 1.0 } else { (PI * CUTOFF * t).sin() / (PI * CUTOFF * t) };
+            let blackman = if t.abs() < half_width {
+                0.42 + 0.5 * (PI * t / half_width).cos() + 0.08 * (2.0 * PI * t / half_width).cos()
+            } else {
+                0.0
+            };
+            *weight = sinc * blackman;
+        }
+
+        let sum: f64 = weights.iter().sum();
+        for (tap, weight) in taps.iter_mut().zip(weights.iter()) {
+            *tap = (weight / sum) as f32;
+        }
+    }
+    kernel
+}
@@ -0,0 +1,79 @@
+use crate::savestate::{StateError, StateReader, StateWriter};
+
+pub struct VolumeEnvelope {
+    initial_volume: u8, // NRx2 bits 4-7
+    increase: bool,     // NRx2 bit 3
+    period: u8,         // NRx2 bits 0-2, 0 disables the envelope
+    timer: u8,
+    volume: u8,
+}
+
+impl VolumeEnvelope {
+    pub fn new() -> Self {
+        VolumeEnvelope {
+            initial_volume: 0,
+            increase: false,
+            period: 0,
+            timer: 0,
+            volume: 0,
+        }
+    }
+
+    pub fn write(&mut self, value: u8) {
+        self.initial_volume = value >> 4;
+        self.increase = value & 0x08 != 0;
+        self.period = value & 0x07;
+    }
+
+    pub fn trigger(&mut self) {
+        self.volume = self.initial_volume;
+        self.timer = self.reload_value();
+    }
+
+    fn reload_value(&self) -> u8 {
+        if self.period == 0 { 8 } else { self.period }
+    }
+
+    pub fn clock(&mut self) {
+        if self.period == 0 {
+            return;
+        }
+
+        if self.timer > 0 {
+            self.timer -= 1;
+        }
+
+        if self.timer == 0 {
+            self.timer = self.reload_value();
+            if self.increase && self.volume < 15 {
+                self.volume += 1;
+            } else if !self.increase && self.volume > 0 {
+                self.volume -= 1;
+            }
+        }
+    }
+
+    pub fn volume(&self) -> u8 {
+        self.volume
+    }
+
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        writer.write_u8(self.initial_volume);
+        writer.write_bool(self.increase);
+        writer.write_u8(self.period);
+        writer.write_u8(self.timer);
+        writer.write_u8(self.volume);
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.initial_volume = reader.read_u8()?;
+        self.increase = reader.read_bool()?;
+        self.period = reader.read_u8()?;
+        self.timer = reader.read_u8()?;
+        self.volume = reader.read_u8()?;
+        if self.initial_volume > 15 || self.volume > 15 {
+            return Err(StateError::InvalidData);
+        }
+        Ok(())
+    }
+}
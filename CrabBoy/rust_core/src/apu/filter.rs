@@ -0,0 +1,38 @@
+#[derive(Clone, Copy, PartialEq)]
+pub enum HighPassModel {
+    Dmg,
+    Cgb,
+}
+
+impl HighPassModel {
+    // Fraction of the capacitor charge kept per CPU cycle.
+    fn charge_per_cycle(self) -> f64 {
+        match self {
+            HighPassModel::Dmg => 0.999958,
+            HighPassModel::Cgb => 0.998943,
+        }
+    }
+}
+
+// Models the capacitor the console places in series with its audio output, which removes the
+// DC offset introduced by the channel DACs.
+pub struct HighPassFilter {
+    charge_factor: f32,
+    capacitor: f32,
+}
+
+impl HighPassFilter {
+    pub fn new(model: HighPassModel, clock_rate: u32, sample_rate: u32) -> Self {
+        let cycles_per_sample = clock_rate as f64 / sample_rate as f64;
+        HighPassFilter {
+            charge_factor: model.charge_per_cycle().powf(cycles_per_sample) as f32,
+            capacitor: 0.0,
+        }
+    }
+
+    pub fn apply(&mut self, input: f32) -> f32 {
+        let output = input - self.capacitor;
+        self.capacitor = input - output * self.charge_factor;
+        output
+    }
+}
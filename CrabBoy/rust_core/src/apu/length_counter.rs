@@ -0,0 +1,58 @@
+use crate::savestate::{StateError, StateReader, StateWriter};
+
+pub struct LengthCounter {
+    enabled: bool, // NRx4 bit 6
+    counter: u16,
+    max: u16,      // 64 for channels 1, 2 and 4, 256 for channel 3
+}
+
+impl LengthCounter {
+    pub fn new(max: u16) -> Self {
+        LengthCounter {
+            enabled: false,
+            counter: 0,
+            max,
+        }
+    }
+
+    pub fn load(&mut self, length: u8) {
+        self.counter = self.max - length as u16;
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn trigger(&mut self) {
+        if self.counter == 0 {
+            self.counter = self.max;
+        }
+    }
+
+    // Returns true when the counter has just run out and the channel should be disabled.
+    pub fn clock(&mut self) -> bool {
+        if self.enabled && self.counter > 0 {
+            self.counter -= 1;
+            return self.counter == 0;
+        }
+        false
+    }
+
+    pub fn remaining(&self) -> u16 {
+        self.counter
+    }
+
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        writer.write_bool(self.enabled);
+        writer.write_u16(self.counter);
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.enabled = reader.read_bool()?;
+        self.counter = reader.read_u16()?;
+        if self.counter > self.max {
+            return Err(StateError::InvalidData);
+        }
+        Ok(())
+    }
+}
@@ -1,3 +1,40 @@
+mod square;
+mod wave;
+mod noise;
+mod sweep;
+mod envelope;
+mod length_counter;
+mod blip;
+mod filter;
+mod wav;
+
+use square::SquareChannel;
+use wave::WaveChannel;
+use noise::NoiseChannel;
+use blip::BlipBuffer;
+use filter::HighPassFilter;
+
+pub use filter::HighPassModel;
+
+use crate::savestate::{StateError, StateReader, StateWriter};
+
+const CPU_CLOCK_HZ: u32 = 4_194_304;
+const DEFAULT_SAMPLE_RATE: u32 = 44_100;
+const MIN_SAMPLE_RATE: u32 = 8_000;
+const MAX_SAMPLE_RATE: u32 = 192_000;
+pub const FRAME_SEQUENCER_PERIOD: u32 = CPU_CLOCK_HZ / 512;
+const BLIP_FLUSH_PERIOD: u32 = 4096; // Cycles between reads from the band-limited buffers
+const MAX_RATE_DEVIATION: f64 = 0.005; // Largest resampling ratio change used by dynamic rate control
+
+#[derive(Clone)]
+pub struct ChannelState {
+    pub enabled: bool,
+    pub frequency_hz: f64,     // Tone frequency, or the LFSR clock rate for the noise channel
+    pub volume: u8,            // Current volume (0-15)
+    pub duty: u8,              // Duty pattern index, only meaningful for the square channels
+    pub length_remaining: u16, // Length counter steps left before the channel is silenced
+}
+
 pub struct APU {
     nr10: u8, // 0xFF10 - NR10: Channel 1 Sweep Register
     nr11: u8, // 0xFF11 - NR11: Channel 1 Sound Length/Wave Pattern Duty
@@ -30,6 +67,31 @@ pub struct APU {
 
     io: [u8; 9],
     wave_pattern_ram: [u8; 16],
+
+    channel1: SquareChannel,
+    channel2: SquareChannel,
+    channel3: WaveChannel,
+    channel4: NoiseChannel,
+
+    frame_sequencer_step: u8, // Current frame sequencer step (0-7)
+
+    sample_rate: u32,         // Output sample rate requested by the host
+    rate_adjustment: f64,     // Resampling ratio correction from dynamic rate control
+    speed: f64,               // Emulation speed multiplier, output is resampled so playback keeps real time
+    high_pass_model: HighPassModel,
+    blip_left: BlipBuffer,
+    blip_right: BlipBuffer,
+    high_pass_left: HighPassFilter,
+    high_pass_right: HighPassFilter,
+    blip_clock: u32,          // Cycles since the band-limited buffers were last read
+    last_left: f32,           // Last amplitude written to the left buffer
+    last_right: f32,          // Last amplitude written to the right buffer
+    sample_buffer: Vec<f32>,  // Interleaved stereo samples
+
+    recording: Option<Vec<i16>>, // Interleaved stereo PCM captured since recording started
+
+    muted_channels: [bool; 4],
+    soloed_channels: [bool; 4],
 }
 
 impl APU {
@@ -60,9 +122,70 @@ impl APU {
             nr52: 0,
             io: [0; 9],
             wave_pattern_ram: [0; 16],
+            channel1: SquareChannel::new(true),
+            channel2: SquareChannel::new(false),
+            channel3: WaveChannel::new(),
+            channel4: NoiseChannel::new(),
+            frame_sequencer_step: 0,
+            sample_rate: DEFAULT_SAMPLE_RATE,
+            rate_adjustment: 1.0,
+            speed: 1.0,
+            high_pass_model: HighPassModel::Dmg,
+            blip_left: BlipBuffer::new(CPU_CLOCK_HZ, DEFAULT_SAMPLE_RATE),
+            blip_right: BlipBuffer::new(CPU_CLOCK_HZ, DEFAULT_SAMPLE_RATE),
+            high_pass_left: HighPassFilter::new(HighPassModel::Dmg, CPU_CLOCK_HZ, DEFAULT_SAMPLE_RATE),
+            high_pass_right: HighPassFilter::new(HighPassModel::Dmg, CPU_CLOCK_HZ, DEFAULT_SAMPLE_RATE),
+            blip_clock: 0,
+            last_left: 0.0,
+            last_right: 0.0,
+            sample_buffer: Vec::new(),
+            recording: None,
+            muted_channels: [false; 4],
+            soloed_channels: [false; 4],
         }
     }
 
+    // Only the emulated hardware is stored, host side output settings and buffers are kept as they are.
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        let registers = [
+            self.nr10, self.nr11, self.nr12, self.nr13, self.nr14,
+            self.nr21, self.nr22, self.nr23, self.nr24,
+            self.nr30, self.nr31, self.nr32, self.nr33, self.nr34,
+            self.single_memory_cell1, self.single_memory_cell2,
+            self.nr41, self.nr42, self.nr43, self.nr44,
+            self.nr50, self.nr51, self.nr52,
+        ];
+        writer.write_bytes(&registers);
+        writer.write_bytes(&self.io);
+        writer.write_bytes(&self.wave_pattern_ram);
+        self.channel1.save_state(writer);
+        self.channel2.save_state(writer);
+        self.channel3.save_state(writer);
+        self.channel4.save_state(writer);
+        writer.write_u8(self.frame_sequencer_step);
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        let mut registers = [0; 23];
+        reader.read_bytes(&mut registers)?;
+        [
+            self.nr10, self.nr11, self.nr12, self.nr13, self.nr14,
+            self.nr21, self.nr22, self.nr23, self.nr24,
+            self.nr30, self.nr31, self.nr32, self.nr33, self.nr34,
+            self.single_memory_cell1, self.single_memory_cell2,
+            self.nr41, self.nr42, self.nr43, self.nr44,
+            self.nr50, self.nr51, self.nr52,
+        ] = registers;
+        reader.read_bytes(&mut self.io)?;
+        reader.read_bytes(&mut self.wave_pattern_ram)?;
+        self.channel1.load_state(reader)?;
+        self.channel2.load_state(reader)?;
+        self.channel3.load_state(reader)?;
+        self.channel4.load_state(reader)?;
+        self.frame_sequencer_step = reader.read_u8()? & 0x07;
+        Ok(())
+    }
+
     pub fn read_byte(&self, address: u16) -> u8 {
         match address {
             0xFF10 => self.nr10,
@@ -87,41 +210,353 @@ impl APU {
             0xFF23 => self.nr44,
             0xFF24 => self.nr50,
             0xFF25 => self.nr51,
-            0xFF26 => self.nr52,
+            0xFF26 => 0x70 | self.nr52 | self.channel_status(),
             0xFF27..=0xFF2F => self.io[address as usize - 0xFF27],
             0xFF30..=0xFF3F => self.wave_pattern_ram[address as usize - 0xFF30],
-            _ => panic!("Attempted to read from an invalid memory address: {:04X}", address),
+            _ => 0xFF,
         }
     }
 
     pub fn write_byte(&mut self, address: u16, value: u8) {
+        if !self.is_powered_on() && (0xFF10..=0xFF25).contains(&address) {
+            return;
+        }
+
         match address {
-            0xFF10 => self.nr10 = value,
-            0xFF11 => self.nr11 = value,
-            0xFF12 => self.nr12 = value,
-            0xFF13 => self.nr13 = value,
-            0xFF14 => self.nr14 = value,
+            0xFF10 => {
+                self.nr10 = value;
+                self.channel1.write_sweep(value);
+            },
+            0xFF11 => {
+                self.nr11 = value;
+                self.channel1.write_length_duty(value);
+            },
+            0xFF12 => {
+                self.nr12 = value;
+                self.channel1.write_envelope(value);
+            },
+            0xFF13 => {
+                self.nr13 = value;
+                self.channel1.write_frequency_low(value);
+            },
+            0xFF14 => {
+                self.nr14 = value;
+                self.channel1.write_frequency_high(value);
+            },
             0xFF15 => self.single_memory_cell2 = value,
-            0xFF16 => self.nr21 = value,
-            0xFF17 => self.nr22 = value,
-            0xFF18 => self.nr23 = value,
-            0xFF19 => self.nr24 = value,
-            0xFF1A => self.nr30 = value,
-            0xFF1B => self.nr31 = value,
-            0xFF1C => self.nr32 = value,
-            0xFF1D => self.nr33 = value,
-            0xFF1E => self.nr34 = value,
+            0xFF16 => {
+                self.nr21 = value;
+                self.channel2.write_length_duty(value);
+            },
+            0xFF17 => {
+                self.nr22 = value;
+                self.channel2.write_envelope(value);
+            },
+            0xFF18 => {
+                self.nr23 = value;
+                self.channel2.write_frequency_low(value);
+            },
+            0xFF19 => {
+                self.nr24 = value;
+                self.channel2.write_frequency_high(value);
+            },
+            0xFF1A => {
+                self.nr30 = value;
+                self.channel3.write_dac(value);
+            },
+            0xFF1B => {
+                self.nr31 = value;
+                self.channel3.write_length(value);
+            },
+            0xFF1C => {
+                self.nr32 = value;
+                self.channel3.write_output_level(value);
+            },
+            0xFF1D => {
+                self.nr33 = value;
+                self.channel3.write_frequency_low(value);
+            },
+            0xFF1E => {
+                self.nr34 = value;
+                self.channel3.write_frequency_high(value);
+            },
             0xFF1F => self.single_memory_cell1 = value,
-            0xFF20 => self.nr41 = value,
-            0xFF21 => self.nr42 = value,
-            0xFF22 => self.nr43 = value,
-            0xFF23 => self.nr44 = value,
+            0xFF20 => {
+                self.nr41 = value;
+                self.channel4.write_length(value);
+            },
+            0xFF21 => {
+                self.nr42 = value;
+                self.channel4.write_envelope(value);
+            },
+            0xFF22 => {
+                self.nr43 = value;
+                self.channel4.write_polynomial_counter(value);
+            },
+            0xFF23 => {
+                self.nr44 = value;
+                self.channel4.write_control(value);
+            },
             0xFF24 => self.nr50 = value,
             0xFF25 => self.nr51 = value,
-            0xFF26 => self.nr52 = value,
+            0xFF26 => {
+                let power_on = value & 0x80 != 0;
+                if !power_on && self.is_powered_on() {
+                    self.power_off();
+                } else if power_on && !self.is_powered_on() {
+                    self.frame_sequencer_step = 0;
+                }
+                self.nr52 = value & 0x80;
+            },
             0xFF27..=0xFF2F => self.io[address as usize - 0xFF27] = value,
             0xFF30..=0xFF3F => self.wave_pattern_ram[address as usize - 0xFF30] = value,
-            _ => panic!("Attempted to write to an invalid memory address: {:04X}", address),
+            _ => (),
+        }
+    }
+
+    pub fn is_powered_on(&self) -> bool {
+        self.nr52 & 0x80 != 0
+    }
+
+    fn power_off(&mut self) {
+        for address in 0xFF10..=0xFF25 {
+            self.write_byte(address, 0);
+        }
+        self.channel1 = SquareChannel::new(true);
+        self.channel2 = SquareChannel::new(false);
+        self.channel3 = WaveChannel::new();
+        self.channel4 = NoiseChannel::new();
+    }
+
+    fn channel_status(&self) -> u8 {
+        (self.channel1.is_enabled() as u8)
+            | (self.channel2.is_enabled() as u8) << 1
+            | (self.channel3.is_enabled() as u8) << 2
+            | (self.channel4.is_enabled() as u8) << 3
+    }
+
+    pub fn tick(&mut self, cycles: u32) {
+        let mut remaining = cycles;
+        while remaining > 0 {
+            let step = remaining.min(4);
+            remaining -= step;
+
+            if self.is_powered_on() {
+                self.channel1.tick(step);
+                self.channel2.tick(step);
+                self.channel3.tick(step);
+                self.channel4.tick(step);
+            }
+
+            self.blip_clock += step;
+            let (left, right) = self.mix();
+            if left != self.last_left {
+                self.blip_left.add_delta(self.blip_clock, left - self.last_left);
+                self.last_left = left;
+            }
+            if right != self.last_right {
+                self.blip_right.add_delta(self.blip_clock, right - self.last_right);
+                self.last_right = right;
+            }
+
+            if self.blip_clock >= BLIP_FLUSH_PERIOD {
+                self.flush_samples();
+            }
+        }
+    }
+
+    fn flush_samples(&mut self) {
+        self.blip_left.end_frame(self.blip_clock);
+        self.blip_right.end_frame(self.blip_clock);
+        self.blip_clock = 0;
+
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        self.blip_left.read_samples(&mut left);
+        self.blip_right.read_samples(&mut right);
+
+        for (left, right) in left.into_iter().zip(right) {
+            let left = self.high_pass_left.apply(left);
+            let right = self.high_pass_right.apply(right);
+            self.sample_buffer.push(left);
+            self.sample_buffer.push(right);
+
+            if let Some(ref mut recording) = self.recording {
+                recording.push(to_pcm16(left));
+                recording.push(to_pcm16(right));
+            }
+        }
+    }
+
+    // Step   Length Ctr  Vol Env     Sweep
+    // 0      Clock       -           -
+    // 1      -           -           -
+    // 2      Clock       -           Clock
+    // 3      -           -           -
+    // 4      Clock       -           -
+    // 5      -           -           -
+    // 6      Clock       -           Clock
+    // 7      -           Clock       -
+    pub fn step_frame_sequencer(&mut self) {
+        if self.frame_sequencer_step & 0x01 == 0 {
+            self.channel1.clock_length();
+            self.channel2.clock_length();
+            self.channel3.clock_length();
+            self.channel4.clock_length();
+        }
+
+        if self.frame_sequencer_step == 2 || self.frame_sequencer_step == 6 {
+            self.channel1.clock_sweep();
+        }
+
+        if self.frame_sequencer_step == 7 {
+            self.channel1.clock_envelope();
+            self.channel2.clock_envelope();
+            self.channel4.clock_envelope();
+        }
+
+        self.frame_sequencer_step = (self.frame_sequencer_step + 1) & 0x07;
+    }
+
+    // NR51 selects which channels reach each terminal and NR50 scales each terminal's volume (1-8).
+    fn mix(&self) -> (f32, f32) {
+        if !self.is_powered_on() {
+            return (0.0, 0.0);
+        }
+
+        let outputs = [
+            dac_output(self.channel1.output(), self.channel1.is_dac_enabled()),
+            dac_output(self.channel2.output(), self.channel2.is_dac_enabled()),
+            dac_output(self.channel3.output(&self.wave_pattern_ram), self.channel3.is_dac_enabled()),
+            dac_output(self.channel4.output(), self.channel4.is_dac_enabled()),
+        ];
+
+        let mut left = 0.0;
+        let mut right = 0.0;
+        for (channel, output) in outputs.iter().enumerate() {
+            if !self.is_channel_audible(channel) {
+                continue;
+            }
+            if self.nr51 & (0x10 << channel) != 0 {
+                left += output;
+            }
+            if self.nr51 & (0x01 << channel) != 0 {
+                right += output;
+            }
         }
+
+        let left_volume = ((self.nr50 >> 4) & 0x07) as f32 + 1.0;
+        let right_volume = (self.nr50 & 0x07) as f32 + 1.0;
+        (left / 4.0 * left_volume / 8.0, right / 4.0 * right_volume / 8.0)
+    }
+
+    fn is_channel_audible(&self, channel: usize) -> bool {
+        if self.muted_channels[channel] {
+            return false;
+        }
+        !self.soloed_channels.contains(&true) || self.soloed_channels[channel]
+    }
+
+    pub fn set_channel_muted(&mut self, channel: usize, muted: bool) {
+        if let Some(flag) = self.muted_channels.get_mut(channel) {
+            *flag = muted;
+        }
+    }
+
+    pub fn set_channel_soloed(&mut self, channel: usize, soloed: bool) {
+        if let Some(flag) = self.soloed_channels.get_mut(channel) {
+            *flag = soloed;
+        }
+    }
+
+    pub fn channel_states(&self) -> Vec<ChannelState> {
+        vec![
+            self.channel1.state(),
+            self.channel2.state(),
+            self.channel3.state(),
+            self.channel4.state(),
+        ]
+    }
+
+    // Rates outside 8-192 kHz are clamped, the resampler needs the output well below the APU clock.
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        self.sample_rate = sample_rate.clamp(MIN_SAMPLE_RATE, MAX_SAMPLE_RATE);
+        self.reset_output();
+    }
+
+    pub fn set_high_pass_model(&mut self, model: HighPassModel) {
+        self.high_pass_model = model;
+        self.reset_output();
+    }
+
+    fn reset_output(&mut self) {
+        self.blip_left = BlipBuffer::new(CPU_CLOCK_HZ, self.sample_rate);
+        self.blip_right = BlipBuffer::new(CPU_CLOCK_HZ, self.sample_rate);
+        self.high_pass_left = HighPassFilter::new(self.high_pass_model, CPU_CLOCK_HZ, self.sample_rate);
+        self.high_pass_right = HighPassFilter::new(self.high_pass_model, CPU_CLOCK_HZ, self.sample_rate);
+        self.blip_clock = 0;
+        self.last_left = 0.0;
+        self.last_right = 0.0;
+        self.sample_buffer.clear();
+        self.apply_output_rate();
+    }
+
+    // Must be called with the band-limited buffers flushed.
+    fn apply_output_rate(&mut self) {
+        let sample_rate = self.sample_rate as f64 * self.rate_adjustment / self.speed;
+        self.blip_left.set_rates(CPU_CLOCK_HZ, sample_rate);
+        self.blip_right.set_rates(CPU_CLOCK_HZ, sample_rate);
+    }
+
+    // Dynamic rate control: nudges the resampling ratio so that a host buffer filled below half
+    // gets slightly more samples per emulated second, and one filled above half slightly fewer.
+    pub fn adjust_rate(&mut self, buffer_fill: f32) {
+        self.flush_samples();
+        self.rate_adjustment = 1.0 + MAX_RATE_DEVIATION * (1.0 - 2.0 * buffer_fill.clamp(0.0, 1.0) as f64);
+        self.apply_output_rate();
+    }
+
+    // At 2x speed every emulated second is squeezed into half a second of output and so on, which keeps
+    // the host buffer fed at the same pace and raises the pitch instead of producing stutter.
+    pub fn set_speed(&mut self, speed: f64) {
+        self.flush_samples();
+        self.speed = speed;
+        self.apply_output_rate();
+    }
+
+    pub fn buffered_sample_frames(&self) -> usize {
+        self.sample_buffer.len() / 2
+    }
+
+    pub fn take_samples(&mut self) -> Vec<f32> {
+        self.flush_samples();
+        std::mem::take(&mut self.sample_buffer)
+    }
+
+    pub fn clear_samples(&mut self) {
+        self.sample_buffer.clear();
+    }
+
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Vec::new());
+    }
+
+    pub fn stop_recording(&mut self) -> Option<Vec<u8>> {
+        self.flush_samples();
+        self.recording
+            .take()
+            .map(|samples| wav::encode_wav(&samples, self.sample_rate, 2))
+    }
+}
+
+pub fn to_pcm16(sample: f32) -> i16 {
+    (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16
+}
+
+fn dac_output(digital: u8, dac_enabled: bool) -> f32 {
+    if dac_enabled {
+        digital as f32 / 7.5 - 1.0
+    } else {
+        0.0
     }
 }
@@ -0,0 +1,151 @@
+use super::{ChannelState, CPU_CLOCK_HZ};
+use super::envelope::VolumeEnvelope;
+use super::length_counter::LengthCounter;
+use crate::savestate::{StateError, StateReader, StateWriter};
+
+const DIVISORS: [u32; 8] = [8, 16, 32, 48, 64, 80, 96, 112];
+
+pub struct NoiseChannel {
+    enabled: bool,
+    dac_enabled: bool,
+    clock_shift: u8,      // NR43 bits 4-7
+    width_mode: bool,     // NR43 bit 3, 7-bit LFSR when set
+    divisor_code: u8,     // NR43 bits 0-2
+    lfsr: u16,            // 15-bit linear feedback shift register
+    frequency_timer: u32, // Cycles left until the next LFSR shift
+    length: LengthCounter,
+    envelope: VolumeEnvelope,
+}
+
+impl NoiseChannel {
+    pub fn new() -> Self {
+        NoiseChannel {
+            enabled: false,
+            dac_enabled: false,
+            clock_shift: 0,
+            width_mode: false,
+            divisor_code: 0,
+            lfsr: 0x7FFF,
+            frequency_timer: DIVISORS[0],
+            length: LengthCounter::new(64),
+            envelope: VolumeEnvelope::new(),
+        }
+    }
+
+    pub fn write_length(&mut self, value: u8) {
+        self.length.load(value & 0x3F);
+    }
+
+    pub fn write_envelope(&mut self, value: u8) {
+        self.envelope.write(value);
+        self.dac_enabled = value & 0xF8 != 0;
+        if !self.dac_enabled {
+            self.enabled = false;
+        }
+    }
+
+    pub fn write_polynomial_counter(&mut self, value: u8) {
+        self.clock_shift = value >> 4;
+        self.width_mode = value & 0x08 != 0;
+        self.divisor_code = value & 0x07;
+    }
+
+    pub fn write_control(&mut self, value: u8) {
+        self.length.set_enabled(value & 0x40 != 0);
+        if value & 0x80 != 0 {
+            self.trigger();
+        }
+    }
+
+    fn trigger(&mut self) {
+        self.enabled = self.dac_enabled;
+        self.frequency_timer = self.period();
+        self.lfsr = 0x7FFF;
+        self.length.trigger();
+        self.envelope.trigger();
+    }
+
+    fn period(&self) -> u32 {
+        DIVISORS[self.divisor_code as usize] << self.clock_shift
+    }
+
+    pub fn tick(&mut self, cycles: u32) {
+        let mut remaining = cycles;
+        while remaining >= self.frequency_timer {
+            remaining -= self.frequency_timer;
+            self.frequency_timer = self.period();
+            self.shift_lfsr();
+        }
+        self.frequency_timer -= remaining;
+    }
+
+    fn shift_lfsr(&mut self) {
+        let xor = (self.lfsr & 0x01) ^ ((self.lfsr >> 1) & 0x01);
+        self.lfsr = (self.lfsr >> 1) | (xor << 14);
+        if self.width_mode {
+            self.lfsr = (self.lfsr & !(1 << 6)) | (xor << 6);
+        }
+    }
+
+    pub fn clock_length(&mut self) {
+        if self.length.clock() {
+            self.enabled = false;
+        }
+    }
+
+    pub fn clock_envelope(&mut self) {
+        self.envelope.clock();
+    }
+
+    pub fn output(&self) -> u8 {
+        if !self.enabled {
+            return 0;
+        }
+        (!self.lfsr & 0x01) as u8 * self.envelope.volume()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn is_dac_enabled(&self) -> bool {
+        self.dac_enabled
+    }
+
+    pub fn state(&self) -> ChannelState {
+        ChannelState {
+            enabled: self.enabled,
+            frequency_hz: CPU_CLOCK_HZ as f64 / self.period() as f64,
+            volume: self.envelope.volume(),
+            duty: 0,
+            length_remaining: self.length.remaining(),
+        }
+    }
+
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        writer.write_bool(self.enabled);
+        writer.write_bool(self.dac_enabled);
+        writer.write_u8(self.clock_shift);
+        writer.write_bool(self.width_mode);
+        writer.write_u8(self.divisor_code);
+        writer.write_u16(self.lfsr);
+        writer.write_u32(self.frequency_timer);
+        self.length.save_state(writer);
+        self.envelope.save_state(writer);
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.enabled = reader.read_bool()?;
+        self.dac_enabled = reader.read_bool()?;
+        self.clock_shift = reader.read_u8()?;
+        self.width_mode = reader.read_bool()?;
+        self.divisor_code = reader.read_u8()?;
+        self.lfsr = reader.read_u16()?;
+        self.frequency_timer = reader.read_u32()?;
+        if self.clock_shift > 15 || self.divisor_code > 7 {
+            return Err(StateError::InvalidData);
+        }
+        self.length.load_state(reader)?;
+        self.envelope.load_state(reader)
+    }
+}
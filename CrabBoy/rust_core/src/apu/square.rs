@@ -0,0 +1,178 @@
+use super::ChannelState;
+use super::envelope::VolumeEnvelope;
+use super::length_counter::LengthCounter;
+use super::sweep::{FrequencySweep, SweepOutcome};
+use crate::savestate::{StateError, StateReader, StateWriter};
+
+const DUTY_PATTERNS: [[u8; 8]; 4] = [
+    [0, 0, 0, 0, 0, 0, 0, 1], // 12.5%
+    [1, 0, 0, 0, 0, 0, 0, 1], // 25%
+    [1, 0, 0, 0, 0, 1, 1, 1], // 50%
+    [0, 1, 1, 1, 1, 1, 1, 0], // 75%
+];
+
+pub struct SquareChannel {
+    enabled: bool,
+    dac_enabled: bool,
+    duty: u8,             // Selected duty pattern (NRx1 bits 6-7)
+    duty_position: u8,    // Current step inside the duty pattern (0-7)
+    frequency: u16,       // 11-bit frequency value (NRx3 + NRx4 bits 0-2)
+    frequency_timer: u32, // Cycles left until the next duty step
+    length: LengthCounter,
+    envelope: VolumeEnvelope,
+    sweep: Option<FrequencySweep>, // Only channel 1 has a sweep unit
+}
+
+impl SquareChannel {
+    pub fn new(with_sweep: bool) -> Self {
+        SquareChannel {
+            enabled: false,
+            dac_enabled: false,
+            duty: 0,
+            duty_position: 0,
+            frequency: 0,
+            frequency_timer: 2048 * 4,
+            length: LengthCounter::new(64),
+            envelope: VolumeEnvelope::new(),
+            sweep: if with_sweep { Some(FrequencySweep::new()) } else { None },
+        }
+    }
+
+    pub fn write_sweep(&mut self, value: u8) {
+        if let Some(ref mut sweep) = self.sweep {
+            sweep.write(value);
+        }
+    }
+
+    pub fn write_length_duty(&mut self, value: u8) {
+        self.duty = value >> 6;
+        self.length.load(value & 0x3F);
+    }
+
+    pub fn write_envelope(&mut self, value: u8) {
+        self.envelope.write(value);
+        self.dac_enabled = value & 0xF8 != 0;
+        if !self.dac_enabled {
+            self.enabled = false;
+        }
+    }
+
+    pub fn write_frequency_low(&mut self, value: u8) {
+        self.frequency = (self.frequency & 0x0700) | value as u16;
+    }
+
+    pub fn write_frequency_high(&mut self, value: u8) {
+        self.frequency = (self.frequency & 0x00FF) | ((value as u16 & 0x07) << 8);
+        self.length.set_enabled(value & 0x40 != 0);
+        if value & 0x80 != 0 {
+            self.trigger();
+        }
+    }
+
+    fn trigger(&mut self) {
+        self.enabled = self.dac_enabled;
+        self.frequency_timer = self.period();
+        self.length.trigger();
+        self.envelope.trigger();
+
+        if let Some(ref mut sweep) = self.sweep {
+            if !sweep.trigger(self.frequency) {
+                self.enabled = false;
+            }
+        }
+    }
+
+    fn period(&self) -> u32 {
+        (2048 - self.frequency as u32) * 4
+    }
+
+    pub fn tick(&mut self, cycles: u32) {
+        let mut remaining = cycles;
+        while remaining >= self.frequency_timer {
+            remaining -= self.frequency_timer;
+            self.frequency_timer = self.period();
+            self.duty_position = (self.duty_position + 1) & 0x07;
+        }
+        self.frequency_timer -= remaining;
+    }
+
+    pub fn clock_length(&mut self) {
+        if self.length.clock() {
+            self.enabled = false;
+        }
+    }
+
+    pub fn clock_envelope(&mut self) {
+        self.envelope.clock();
+    }
+
+    pub fn clock_sweep(&mut self) {
+        let outcome = match self.sweep {
+            Some(ref mut sweep) => sweep.clock(),
+            None => return,
+        };
+
+        match outcome {
+            SweepOutcome::Unchanged => {},
+            SweepOutcome::Frequency(frequency) => self.frequency = frequency,
+            SweepOutcome::Overflow => self.enabled = false,
+        }
+    }
+
+    pub fn output(&self) -> u8 {
+        if !self.enabled {
+            return 0;
+        }
+        DUTY_PATTERNS[self.duty as usize][self.duty_position as usize] * self.envelope.volume()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn is_dac_enabled(&self) -> bool {
+        self.dac_enabled
+    }
+
+    pub fn state(&self) -> ChannelState {
+        ChannelState {
+            enabled: self.enabled,
+            frequency_hz: 131072.0 / (2048 - self.frequency as u32) as f64,
+            volume: self.envelope.volume(),
+            duty: self.duty,
+            length_remaining: self.length.remaining(),
+        }
+    }
+
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        writer.write_bool(self.enabled);
+        writer.write_bool(self.dac_enabled);
+        writer.write_u8(self.duty);
+        writer.write_u8(self.duty_position);
+        writer.write_u16(self.frequency);
+        writer.write_u32(self.frequency_timer);
+        self.length.save_state(writer);
+        self.envelope.save_state(writer);
+        if let Some(ref sweep) = self.sweep {
+            sweep.save_state(writer);
+        }
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.enabled = reader.read_bool()?;
+        self.dac_enabled = reader.read_bool()?;
+        self.duty = reader.read_u8()?;
+        self.duty_position = reader.read_u8()?;
+        self.frequency = reader.read_u16()?;
+        self.frequency_timer = reader.read_u32()?;
+        if self.duty > 3 || self.duty_position > 7 || self.frequency > 2047 {
+            return Err(StateError::InvalidData);
+        }
+        self.length.load_state(reader)?;
+        self.envelope.load_state(reader)?;
+        if let Some(ref mut sweep) = self.sweep {
+            sweep.load_state(reader)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+use crate::savestate::{StateError, StateReader, StateWriter};
+
+pub enum SweepOutcome {
+    Unchanged,
+    Frequency(u16),
+    Overflow,
+}
+
+pub struct FrequencySweep {
+    period: u8,            // NR10 bits 4-6
+    negate: bool,          // NR10 bit 3
+    shift: u8,             // NR10 bits 0-2
+    timer: u8,
+    enabled: bool,
+    shadow_frequency: u16,
+}
+
+impl FrequencySweep {
+    pub fn new() -> Self {
+        FrequencySweep {
+            period: 0,
+            negate: false,
+            shift: 0,
+            timer: 0,
+            enabled: false,
+            shadow_frequency: 0,
+        }
+    }
+
+    pub fn write(&mut self, value: u8) {
+        self.period = (value >> 4) & 0x07;
+        self.negate = value & 0x08 != 0;
+        self.shift = value & 0x07;
+    }
+
+    fn reload_value(&self) -> u8 {
+        if self.period == 0 { 8 } else { self.period }
+    }
+
+    // Returns false when the overflow check on trigger disables the channel.
+    pub fn trigger(&mut self, frequency: u16) -> bool {
+        self.shadow_frequency = frequency;
+        self.timer = self.reload_value();
+        self.enabled = self.period != 0 || self.shift != 0;
+
+        if self.shift != 0 {
+            return self.calculate_frequency() <= 2047;
+        }
+        true
+    }
+
+    fn calculate_frequency(&self) -> u16 {
+        let delta = self.shadow_frequency >> self.shift;
+        if self.negate {
+            self.shadow_frequency - delta
+        } else {
+            self.shadow_frequency + delta
+        }
+    }
+
+    pub fn clock(&mut self) -> SweepOutcome {
+        if self.timer > 0 {
+            self.timer -= 1;
+        }
+
+        if self.timer != 0 {
+            return SweepOutcome::Unchanged;
+        }
+
+        self.timer = self.reload_value();
+        if !self.enabled || self.period == 0 {
+            return SweepOutcome::Unchanged;
+        }
+
+        let new_frequency = self.calculate_frequency();
+        if new_frequency > 2047 {
+            return SweepOutcome::Overflow;
+        }
+
+        if self.shift == 0 {
+            return SweepOutcome::Unchanged;
+        }
+
+        self.shadow_frequency = new_frequency;
+        if self.calculate_frequency() > 2047 {
+            return SweepOutcome::Overflow;
+        }
+        SweepOutcome::Frequency(new_frequency)
+    }
+
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        writer.write_u8(self.period);
+        writer.write_bool(self.negate);
+        writer.write_u8(self.shift);
+        writer.write_u8(self.timer);
+        writer.write_bool(self.enabled);
+        writer.write_u16(self.shadow_frequency);
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.period = reader.read_u8()?;
+        self.negate = reader.read_bool()?;
+        self.shift = reader.read_u8()?;
+        self.timer = reader.read_u8()?;
+        self.enabled = reader.read_bool()?;
+        self.shadow_frequency = reader.read_u16()?;
+        if self.shift > 7 || self.shadow_frequency > 2047 {
+            return Err(StateError::InvalidData);
+        }
+        Ok(())
+    }
+}
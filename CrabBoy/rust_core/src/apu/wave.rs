@@ -0,0 +1,137 @@
+use super::ChannelState;
+use super::length_counter::LengthCounter;
+use crate::savestate::{StateError, StateReader, StateWriter};
+
+pub struct WaveChannel {
+    enabled: bool,
+    dac_enabled: bool,    // NR30 bit 7
+    output_level: u8,     // NR32 bits 5-6
+    position: u8,         // Current 4-bit sample inside wave RAM (0-31)
+    frequency: u16,       // 11-bit frequency value (NR33 + NR34 bits 0-2)
+    frequency_timer: u32, // Cycles left until the next sample
+    length: LengthCounter,
+}
+
+impl WaveChannel {
+    pub fn new() -> Self {
+        WaveChannel {
+            enabled: false,
+            dac_enabled: false,
+            output_level: 0,
+            position: 0,
+            frequency: 0,
+            frequency_timer: 2048 * 2,
+            length: LengthCounter::new(256),
+        }
+    }
+
+    pub fn write_dac(&mut self, value: u8) {
+        self.dac_enabled = value & 0x80 != 0;
+        if !self.dac_enabled {
+            self.enabled = false;
+        }
+    }
+
+    pub fn write_length(&mut self, value: u8) {
+        self.length.load(value);
+    }
+
+    pub fn write_output_level(&mut self, value: u8) {
+        self.output_level = (value >> 5) & 0x03;
+    }
+
+    pub fn write_frequency_low(&mut self, value: u8) {
+        self.frequency = (self.frequency & 0x0700) | value as u16;
+    }
+
+    pub fn write_frequency_high(&mut self, value: u8) {
+        self.frequency = (self.frequency & 0x00FF) | ((value as u16 & 0x07) << 8);
+        self.length.set_enabled(value & 0x40 != 0);
+        if value & 0x80 != 0 {
+            self.trigger();
+        }
+    }
+
+    fn trigger(&mut self) {
+        self.enabled = self.dac_enabled;
+        self.frequency_timer = self.period();
+        self.position = 0;
+        self.length.trigger();
+    }
+
+    fn period(&self) -> u32 {
+        (2048 - self.frequency as u32) * 2
+    }
+
+    pub fn tick(&mut self, cycles: u32) {
+        let mut remaining = cycles;
+        while remaining >= self.frequency_timer {
+            remaining -= self.frequency_timer;
+            self.frequency_timer = self.period();
+            self.position = (self.position + 1) & 0x1F;
+        }
+        self.frequency_timer -= remaining;
+    }
+
+    pub fn clock_length(&mut self) {
+        if self.length.clock() {
+            self.enabled = false;
+        }
+    }
+
+    pub fn output(&self, wave_pattern_ram: &[u8; 16]) -> u8 {
+        if !self.enabled {
+            return 0;
+        }
+        let byte = wave_pattern_ram[self.position as usize / 2];
+        let sample = if self.position & 0x01 == 0 { byte >> 4 } else { byte & 0x0F };
+        match self.output_level {
+            0 => 0,           // Mute
+            1 => sample,      // 100%
+            2 => sample >> 1, // 50%
+            3 => sample >> 2, // 25%
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn is_dac_enabled(&self) -> bool {
+        self.dac_enabled
+    }
+
+    pub fn state(&self) -> ChannelState {
+        ChannelState {
+            enabled: self.enabled,
+            frequency_hz: 65536.0 / (2048 - self.frequency as u32) as f64,
+            volume: [0, 15, 7, 3][self.output_level as usize],
+            duty: 0,
+            length_remaining: self.length.remaining(),
+        }
+    }
+
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        writer.write_bool(self.enabled);
+        writer.write_bool(self.dac_enabled);
+        writer.write_u8(self.output_level);
+        writer.write_u8(self.position);
+        writer.write_u16(self.frequency);
+        writer.write_u32(self.frequency_timer);
+        self.length.save_state(writer);
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.enabled = reader.read_bool()?;
+        self.dac_enabled = reader.read_bool()?;
+        self.output_level = reader.read_u8()?;
+        self.position = reader.read_u8()?;
+        self.frequency = reader.read_u16()?;
+        self.frequency_timer = reader.read_u32()?;
+        if self.output_level > 3 || self.position > 31 || self.frequency > 2047 {
+            return Err(StateError::InvalidData);
+        }
+        self.length.load_state(reader)
+    }
+}
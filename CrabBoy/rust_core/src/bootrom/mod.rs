@@ -1,27 +1,110 @@
+use crate::cartridge::CARTRIDGE;
+use crate::error::CoreError;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Model {
+    Dmg,
+    Mgb,
+    Cgb,
+}
+
+// Hardware registers as the boot ROM leaves them, written in this order. DMA, LY and the write-only
+// sound bits are left at their power-on values. NR52 comes before the other sound registers, which
+// ignore writes while the APU is off.
+const POST_BOOT_IO: [(u16, u8); 13] = [
+    (0xFF00, 0xCF), // P1
+    (0xFF05, 0x00), // TIMA
+    (0xFF06, 0x00), // TMA
+    (0xFF07, 0xF8), // TAC
+    (0xFF0F, 0xE1), // IF
+    (0xFF26, 0x80), // NR52
+    (0xFF10, 0x80), // NR10
+    (0xFF11, 0x80), // NR11
+    (0xFF12, 0xF3), // NR12
+    (0xFF24, 0x77), // NR50
+    (0xFF25, 0xF3), // NR51
+    (0xFF40, 0x91), // LCDC
+    (0xFF41, 0x85), // STAT
+];
+
+// State the boot ROM hands over to the cartridge at 0x0100, used when no boot ROM is run.
+pub struct PostBootState {
+    pub registers: [u8; 8], // A, F, B, C, D, E, H, L
+    pub io: Vec<(u16, u8)>, // Hardware register writes
+    pub divider: u16,       // DIV in the high byte, the cycles counted towards its next increment in the low byte
+}
+
+impl PostBootState {
+    pub fn new(model: Model, cartridge: &CARTRIDGE) -> Self {
+        // The DMG and MGB boot ROMs clear H and C when the header checksum is zero.
+        let flags = if cartridge.header_checksum == 0 { 0x80 } else { 0xB0 };
+
+        let registers = match model {
+            Model::Dmg => [0x01, flags, 0x00, 0x13, 0x00, 0xD8, 0x01, 0x4D],
+            Model::Mgb => [0xFF, flags, 0x00, 0x13, 0x00, 0xD8, 0x01, 0x4D],
+            Model::Cgb if cartridge.cgb_flag & 0x80 != 0 => [0x11, 0x80, 0x00, 0x00, 0xFF, 0x56, 0x00, 0x0D],
+            Model::Cgb => {
+                // For DMG cartridges the CGB boot ROM picks a compatibility palette from the title, and
+                // leaves the title checksum of Nintendo published games in B.
+                let nintendo = cartridge.old_licensee_code == 0x01
+                    || (cartridge.old_licensee_code == 0x33 && cartridge.new_licensee_code == *b"01");
+                let b = if nintendo {
+                    cartridge.title.iter().fold(cartridge.cgb_flag, |sum, byte| sum.wrapping_add(*byte))
+                } else {
+                    0x00
+                };
+                let [h, l] = if b == 0x43 || b == 0x58 { [0x99, 0x1A] } else { [0x00, 0x7C] };
+                [0x11, 0x80, b, 0x00, 0x00, 0x08, h, l]
+            },
+        };
+
+        let mut io = POST_BOOT_IO.to_vec();
+        io.push((0xFF02, if model == Model::Cgb { 0x7F } else { 0x7E })); // SC
+        if model != Model::Cgb {
+            io.push((0xFF47, 0xFC)); // BGP, the CGB boot ROM sets up color palettes instead
+        }
+
+        PostBootState {
+            registers,
+            io,
+            divider: if model == Model::Cgb { 0x1EA0 } else { 0xABCC },
+        }
+    }
+}
+
+pub const DMG_BOOT_ROM_SIZE: usize = 0x0100;
+pub const CGB_BOOT_ROM_SIZE: usize = 0x0900;
+
 pub struct BOOTROM {
     pub boot_enabled: bool,
-    boot_room: [u8; 256],
+    boot_room: Vec<u8>, // Empty when no boot ROM was supplied
 }
 
 impl BOOTROM {
-    pub fn new() -> Self {
-        let mut bootrom_contents: [u8; 256] = [0x00; 256];
-
-        bootrom_contents[0x00] = 0x31;
-        bootrom_contents[0x01] = 0xFE;
-        bootrom_contents[0x02] = 0xFF;
-        bootrom_contents[0x03] = 0xC3;
-        bootrom_contents[0x04] = 0xFC;
-        bootrom_contents[0x05] = 0x00;
-        bootrom_contents[0xFC] = 0x3E;
-        bootrom_contents[0xFD] = 0x01;
-        bootrom_contents[0xFE] = 0xE0;
-        bootrom_contents[0xFF] = 0x50;
-
-        BOOTROM {
-            boot_enabled: true,
-            boot_room: bootrom_contents,
+    // The image has to match the model, 256 bytes for the DMG and MGB and 2304 bytes for the CGB. Without
+    // one the emulator starts from the post boot state instead.
+    pub fn new(image: Option<Vec<u8>>, model: Model) -> Result<Self, CoreError> {
+        let image = image.unwrap_or_default();
+        let expected = if model == Model::Cgb { CGB_BOOT_ROM_SIZE } else { DMG_BOOT_ROM_SIZE };
+        if !image.is_empty() && image.len() != expected {
+            return Err(CoreError::InvalidBootRom { expected, actual: image.len() });
         }
+
+        Ok(BOOTROM {
+            boot_enabled: !image.is_empty(),
+            boot_room: image,
+        })
+    }
+
+    pub fn is_present(&self) -> bool {
+        !self.boot_room.is_empty()
+    }
+
+    // The CGB boot ROM is split around 0x0100-0x01FF, which keeps showing the cartridge header.
+    pub fn is_mapped(&self, address: u16) -> bool {
+        self.boot_enabled
+            && (address <= 0x00FF || (0x0200..=0x08FF).contains(&address))
+            && (address as usize) < self.boot_room.len()
     }
 
     pub fn read_byte(&self, address: u16) -> u8 {
@@ -2,55 +2,446 @@ use super::*;
 // Section: wire functions
 
 #[no_mangle]
-pub extern "C" fn wire_load_rom(
+pub extern "C" fn wire_create_emulator(
     port_: i64,
     rom_data: *mut wire_uint_8_list,
     ram_data: *mut wire_uint_8_list,
+    boot_rom: *mut wire_uint_8_list,
+    model: u8,
 ) {
-    wire_load_rom_impl(port_, rom_data, ram_data)
+    wire_create_emulator_impl(port_, rom_data, ram_data, boot_rom, model)
 }
 
 #[no_mangle]
-pub extern "C" fn wire_unload_emulator(port_: i64) {
-    wire_unload_emulator_impl(port_)
+pub extern "C" fn wire_destroy_emulator(port_: i64, id: u32) {
+    wire_destroy_emulator_impl(port_, id)
 }
 
 #[no_mangle]
-pub extern "C" fn wire_render_frame(port_: i64) {
-    wire_render_frame_impl(port_)
+pub extern "C" fn wire_render_frame(port_: i64, id: u32) {
+    wire_render_frame_impl(port_, id)
 }
 
 #[no_mangle]
-pub extern "C" fn wire_set_buttons_state(port_: i64, button_states: *mut wire_uint_8_list) {
-    wire_set_buttons_state_impl(port_, button_states)
+pub extern "C" fn wire_set_buttons_state(
+    port_: i64,
+    id: u32,
+    button_states: *mut wire_uint_8_list,
+) {
+    wire_set_buttons_state_impl(port_, id, button_states)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_set_audio_sample_rate(port_: i64, id: u32, sample_rate: u32) {
+    wire_set_audio_sample_rate_impl(port_, id, sample_rate)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_drain_audio_samples(port_: i64, id: u32) {
+    wire_drain_audio_samples_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_set_audio_filter_model(port_: i64, id: u32, cgb: bool) {
+    wire_set_audio_filter_model_impl(port_, id, cgb)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_set_audio_channel_muted(port_: i64, id: u32, channel: u8, muted: bool) {
+    wire_set_audio_channel_muted_impl(port_, id, channel, muted)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_set_audio_channel_soloed(port_: i64, id: u32, channel: u8, soloed: bool) {
+    wire_set_audio_channel_soloed_impl(port_, id, channel, soloed)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_get_audio_channel_states(port_: i64, id: u32) {
+    wire_get_audio_channel_states_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_start_audio_recording(port_: i64, id: u32) {
+    wire_start_audio_recording_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_stop_audio_recording(port_: i64, id: u32) {
+    wire_stop_audio_recording_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_run_until_audio_samples(
+    port_: i64,
+    id: u32,
+    sample_frames: u32,
+    buffer_fill: f32,
+) {
+    wire_run_until_audio_samples_impl(port_, id, sample_frames, buffer_fill)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_save_emulator_state(port_: i64, id: u32) {
+    wire_save_emulator_state_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_load_emulator_state(port_: i64, id: u32, state: *mut wire_uint_8_list) {
+    wire_load_emulator_state_impl(port_, id, state)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_set_rewind_config(
+    port_: i64,
+    id: u32,
+    memory_budget: u32,
+    frames_per_snapshot: u32,
+) {
+    wire_set_rewind_config_impl(port_, id, memory_budget, frames_per_snapshot)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_rewind_emulator(port_: i64, id: u32, frames: u32) {
+    wire_rewind_emulator_impl(port_, id, frames)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_set_emulation_speed(port_: i64, id: u32, speed: f64) {
+    wire_set_emulation_speed_impl(port_, id, speed)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_step_emulator_instruction(port_: i64, id: u32) {
+    wire_step_emulator_instruction_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_step_emulator_scanline(port_: i64, id: u32) {
+    wire_step_emulator_scanline_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_step_emulator_frame(port_: i64, id: u32) {
+    wire_step_emulator_frame_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_get_screen_buffer(port_: i64, id: u32) {
+    wire_get_screen_buffer_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_add_emulator_breakpoint(
+    port_: i64,
+    id: u32,
+    address: u16,
+    bank: *mut u16,
+    condition: *mut wire_uint_8_list,
+) {
+    wire_add_emulator_breakpoint_impl(port_, id, address, bank, condition)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_remove_emulator_breakpoint(
+    port_: i64,
+    id: u32,
+    address: u16,
+    bank: *mut u16,
+) {
+    wire_remove_emulator_breakpoint_impl(port_, id, address, bank)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_add_emulator_watchpoint(
+    port_: i64,
+    id: u32,
+    start: u16,
+    end: u16,
+    read: bool,
+    write: bool,
+    execute: bool,
+) {
+    wire_add_emulator_watchpoint_impl(port_, id, start, end, read, write, execute)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_remove_emulator_watchpoint(port_: i64, id: u32, start: u16, end: u16) {
+    wire_remove_emulator_watchpoint_impl(port_, id, start, end)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_clear_emulator_breakpoints(port_: i64, id: u32) {
+    wire_clear_emulator_breakpoints_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_set_emulator_break_on_interrupt(port_: i64, id: u32, enabled: bool) {
+    wire_set_emulator_break_on_interrupt_impl(port_, id, enabled)
 }
 
 #[no_mangle]
-pub extern "C" fn wire_load(
+pub extern "C" fn wire_get_stop_reason(port_: i64, id: u32) {
+    wire_get_stop_reason_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_resume_emulator(port_: i64, id: u32) {
+    wire_resume_emulator_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_disassemble_memory(port_: i64, id: u32, address: u16, count: u32) {
+    wire_disassemble_memory_impl(port_, id, address, count)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_start_emulator_trace_file(port_: i64, id: u32, path: *mut wire_uint_8_list) {
+    wire_start_emulator_trace_file_impl(port_, id, path)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_start_emulator_trace_buffer(port_: i64, id: u32, capacity: u32) {
+    wire_start_emulator_trace_buffer_impl(port_, id, capacity)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_stop_emulator_trace(port_: i64, id: u32) {
+    wire_stop_emulator_trace_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_get_emulator_trace(port_: i64, id: u32) {
+    wire_get_emulator_trace_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_start_gdb_server(port_: i64, id: u32, port: u16) {
+    wire_start_gdb_server_impl(port_, id, port)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_stop_gdb_server(port_: i64) {
+    wire_stop_gdb_server_impl(port_)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_create(
     port_: i64,
     rom_data: *mut wire_uint_8_list,
     ram_data: *mut wire_uint_8_list,
+    boot_rom: *mut wire_uint_8_list,
+    model: u8,
+) {
+    wire_create_impl(port_, rom_data, ram_data, boot_rom, model)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_destroy(port_: i64, id: u32) {
+    wire_destroy_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_render(port_: i64, id: u32) {
+    wire_render_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_set_buttons(port_: i64, id: u32, button_states: *mut wire_uint_8_list) {
+    wire_set_buttons_impl(port_, id, button_states)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_set_sample_rate(port_: i64, id: u32, sample_rate: u32) {
+    wire_set_sample_rate_impl(port_, id, sample_rate)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_drain_audio(port_: i64, id: u32) {
+    wire_drain_audio_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_set_cgb_audio_filter(port_: i64, id: u32, enabled: bool) {
+    wire_set_cgb_audio_filter_impl(port_, id, enabled)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_mute_channel(port_: i64, id: u32, channel: u8, muted: bool) {
+    wire_mute_channel_impl(port_, id, channel, muted)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_solo_channel(port_: i64, id: u32, channel: u8, soloed: bool) {
+    wire_solo_channel_impl(port_, id, channel, soloed)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_channel_states(port_: i64, id: u32) {
+    wire_channel_states_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_start_recording(port_: i64, id: u32) {
+    wire_start_recording_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_stop_recording(port_: i64, id: u32) {
+    wire_stop_recording_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_render_audio(port_: i64, id: u32, sample_frames: u32, buffer_fill: f32) {
+    wire_render_audio_impl(port_, id, sample_frames, buffer_fill)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_save_state(port_: i64, id: u32) {
+    wire_save_state_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_load_state(port_: i64, id: u32, state: *mut wire_uint_8_list) {
+    wire_load_state_impl(port_, id, state)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_configure_rewind(
+    port_: i64,
+    id: u32,
+    memory_budget: u32,
+    frames_per_snapshot: u32,
+) {
+    wire_configure_rewind_impl(port_, id, memory_budget, frames_per_snapshot)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_rewind(port_: i64, id: u32, frames: u32) {
+    wire_rewind_impl(port_, id, frames)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_set_speed(port_: i64, id: u32, multiplier: f64) {
+    wire_set_speed_impl(port_, id, multiplier)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_step_instruction(port_: i64, id: u32) {
+    wire_step_instruction_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_step_scanline(port_: i64, id: u32) {
+    wire_step_scanline_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_step_frame(port_: i64, id: u32) {
+    wire_step_frame_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_current_frame(port_: i64, id: u32) {
+    wire_current_frame_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_add_breakpoint(
+    port_: i64,
+    id: u32,
+    address: u16,
+    bank: *mut u16,
+    condition: *mut wire_uint_8_list,
+) {
+    wire_add_breakpoint_impl(port_, id, address, bank, condition)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_remove_breakpoint(port_: i64, id: u32, address: u16, bank: *mut u16) {
+    wire_remove_breakpoint_impl(port_, id, address, bank)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_add_watchpoint(
+    port_: i64,
+    id: u32,
+    start: u16,
+    end: u16,
+    read: bool,
+    write: bool,
+    execute: bool,
 ) {
-    wire_load_impl(port_, rom_data, ram_data)
+    wire_add_watchpoint_impl(port_, id, start, end, read, write, execute)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_remove_watchpoint(port_: i64, id: u32, start: u16, end: u16) {
+    wire_remove_watchpoint_impl(port_, id, start, end)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_clear_breakpoints(port_: i64, id: u32) {
+    wire_clear_breakpoints_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_break_on_interrupt(port_: i64, id: u32, enabled: bool) {
+    wire_break_on_interrupt_impl(port_, id, enabled)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_stop_reason(port_: i64, id: u32) {
+    wire_stop_reason_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_resume(port_: i64, id: u32) {
+    wire_resume_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_disassemble(port_: i64, id: u32, address: u16, count: u32) {
+    wire_disassemble_impl(port_, id, address, count)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_trace_to_file(port_: i64, id: u32, path: *mut wire_uint_8_list) {
+    wire_trace_to_file_impl(port_, id, path)
 }
 
 #[no_mangle]
-pub extern "C" fn wire_unload(port_: i64) {
-    wire_unload_impl(port_)
+pub extern "C" fn wire_trace_to_buffer(port_: i64, id: u32, lines: u32) {
+    wire_trace_to_buffer_impl(port_, id, lines)
 }
 
 #[no_mangle]
-pub extern "C" fn wire_render(port_: i64) {
-    wire_render_impl(port_)
+pub extern "C" fn wire_stop_trace(port_: i64, id: u32) {
+    wire_stop_trace_impl(port_, id)
 }
 
 #[no_mangle]
-pub extern "C" fn wire_set_buttons(port_: i64, button_states: *mut wire_uint_8_list) {
-    wire_set_buttons_impl(port_, button_states)
+pub extern "C" fn wire_trace_log(port_: i64, id: u32) {
+    wire_trace_log_impl(port_, id)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_start_gdb(port_: i64, id: u32, port: u16) {
+    wire_start_gdb_impl(port_, id, port)
+}
+
+#[no_mangle]
+pub extern "C" fn wire_stop_gdb(port_: i64) {
+    wire_stop_gdb_impl(port_)
 }
 
 // Section: allocate functions
 
+#[no_mangle]
+pub extern "C" fn new_box_autoadd_u16_0(value: u16) -> *mut u16 {
+    support::new_leak_box_ptr(value)
+}
+
 #[no_mangle]
 pub extern "C" fn new_uint_8_list_0(len: i32) -> *mut wire_uint_8_list {
     let ans = wire_uint_8_list {
@@ -64,6 +455,18 @@ pub extern "C" fn new_uint_8_list_0(len: i32) -> *mut wire_uint_8_list {
 
 // Section: impl Wire2Api
 
+impl Wire2Api<String> for *mut wire_uint_8_list {
+    fn wire2api(self) -> String {
+        let vec: Vec<u8> = self.wire2api();
+        String::from_utf8_lossy(&vec).into_owned()
+    }
+}
+
+impl Wire2Api<u16> for *mut u16 {
+    fn wire2api(self) -> u16 {
+        unsafe { *support::box_from_leak_ptr(self) }
+    }
+}
 impl Wire2Api<Vec<u8>> for *mut wire_uint_8_list {
     fn wire2api(self) -> Vec<u8> {
         unsafe {
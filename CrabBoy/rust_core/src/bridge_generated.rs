@@ -6,7 +6,8 @@
     clippy::unit_arg,
     clippy::double_parens,
     non_snake_case,
-    clippy::too_many_arguments
+    clippy::too_many_arguments,
+    clippy::unnecessary_literal_unwrap
 )]
 // AUTO GENERATED FILE, DO NOT EDIT.
 // Generated by `flutter_rust_bridge`@ 1.80.0.
@@ -20,117 +21,1316 @@ use std::sync::Arc;
 
 // Section: imports
 
+use crate::emulator::FrameOutput;
+use crate::cpu::disasm::Instruction;
+use crate::debugger::StopKind;
+use crate::debugger::StopReason;
+use crate::emulator::MachineState;
+use crate::emulator::AudioSyncOutput;
+use crate::apu::ChannelState;
+
 // Section: wire functions
 
-fn wire_load_rom_impl(
+fn wire_create_emulator_impl(
+    port_: MessagePort,
+    rom_data: impl Wire2Api<Vec<u8>> + UnwindSafe,
+    ram_data: impl Wire2Api<Option<Vec<u8>>> + UnwindSafe,
+    boot_rom: impl Wire2Api<Option<Vec<u8>>> + UnwindSafe,
+    model: impl Wire2Api<u8> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, u32>(
+        WrapInfo {
+            debug_name: "create_emulator",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_rom_data = rom_data.wire2api();
+            let api_ram_data = ram_data.wire2api();
+            let api_boot_rom = boot_rom.wire2api();
+            let api_model = model.wire2api();
+            move |task_callback| create_emulator(api_rom_data, api_ram_data, api_boot_rom, api_model)
+        },
+    )
+}
+fn wire_destroy_emulator_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<u8>>>(
+        WrapInfo {
+            debug_name: "destroy_emulator",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(destroy_emulator(api_id))
+        },
+    )
+}
+fn wire_render_frame_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, mirror_FrameOutput>(
+        WrapInfo {
+            debug_name: "render_frame",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| render_frame(api_id)
+        },
+    )
+}
+fn wire_set_buttons_state_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    button_states: impl Wire2Api<Vec<u8>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "set_buttons_state",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_button_states = button_states.wire2api();
+            move |task_callback| Ok(set_buttons_state(api_id, api_button_states))
+        },
+    )
+}
+fn wire_set_audio_sample_rate_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    sample_rate: impl Wire2Api<u32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "set_audio_sample_rate",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_sample_rate = sample_rate.wire2api();
+            move |task_callback| Ok(set_audio_sample_rate(api_id, api_sample_rate))
+        },
+    )
+}
+fn wire_drain_audio_samples_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<i16>>>(
+        WrapInfo {
+            debug_name: "drain_audio_samples",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(drain_audio_samples(api_id))
+        },
+    )
+}
+fn wire_set_audio_filter_model_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    cgb: impl Wire2Api<bool> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "set_audio_filter_model",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_cgb = cgb.wire2api();
+            move |task_callback| Ok(set_audio_filter_model(api_id, api_cgb))
+        },
+    )
+}
+fn wire_set_audio_channel_muted_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    channel: impl Wire2Api<u8> + UnwindSafe,
+    muted: impl Wire2Api<bool> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "set_audio_channel_muted",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_channel = channel.wire2api();
+            let api_muted = muted.wire2api();
+            move |task_callback| Ok(set_audio_channel_muted(api_id, api_channel, api_muted))
+        },
+    )
+}
+fn wire_set_audio_channel_soloed_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    channel: impl Wire2Api<u8> + UnwindSafe,
+    soloed: impl Wire2Api<bool> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "set_audio_channel_soloed",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_channel = channel.wire2api();
+            let api_soloed = soloed.wire2api();
+            move |task_callback| Ok(set_audio_channel_soloed(api_id, api_channel, api_soloed))
+        },
+    )
+}
+fn wire_get_audio_channel_states_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<mirror_ChannelState>>>(
+        WrapInfo {
+            debug_name: "get_audio_channel_states",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(get_audio_channel_states(api_id))
+        },
+    )
+}
+fn wire_start_audio_recording_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "start_audio_recording",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(start_audio_recording(api_id))
+        },
+    )
+}
+fn wire_stop_audio_recording_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<u8>>>(
+        WrapInfo {
+            debug_name: "stop_audio_recording",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(stop_audio_recording(api_id))
+        },
+    )
+}
+fn wire_run_until_audio_samples_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    sample_frames: impl Wire2Api<u32> + UnwindSafe,
+    buffer_fill: impl Wire2Api<f32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<mirror_AudioSyncOutput>>(
+        WrapInfo {
+            debug_name: "run_until_audio_samples",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_sample_frames = sample_frames.wire2api();
+            let api_buffer_fill = buffer_fill.wire2api();
+            move |task_callback| Ok(run_until_audio_samples(api_id, api_sample_frames, api_buffer_fill))
+        },
+    )
+}
+fn wire_save_emulator_state_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<u8>>>(
+        WrapInfo {
+            debug_name: "save_emulator_state",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(save_emulator_state(api_id))
+        },
+    )
+}
+fn wire_load_emulator_state_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    state: impl Wire2Api<Vec<u8>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "load_emulator_state",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_state = state.wire2api();
+            move |task_callback| load_emulator_state(api_id, api_state)
+        },
+    )
+}
+fn wire_set_rewind_config_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    memory_budget: impl Wire2Api<u32> + UnwindSafe,
+    frames_per_snapshot: impl Wire2Api<u32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "set_rewind_config",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_memory_budget = memory_budget.wire2api();
+            let api_frames_per_snapshot = frames_per_snapshot.wire2api();
+            move |task_callback| Ok(set_rewind_config(api_id, api_memory_budget, api_frames_per_snapshot))
+        },
+    )
+}
+fn wire_rewind_emulator_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    frames: impl Wire2Api<u32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, u32>(
+        WrapInfo {
+            debug_name: "rewind_emulator",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_frames = frames.wire2api();
+            move |task_callback| Ok(rewind_emulator(api_id, api_frames))
+        },
+    )
+}
+fn wire_set_emulation_speed_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    speed: impl Wire2Api<f64> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "set_emulation_speed",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_speed = speed.wire2api();
+            move |task_callback| Ok(set_emulation_speed(api_id, api_speed))
+        },
+    )
+}
+fn wire_step_emulator_instruction_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<mirror_MachineState>>(
+        WrapInfo {
+            debug_name: "step_emulator_instruction",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(step_emulator_instruction(api_id))
+        },
+    )
+}
+fn wire_step_emulator_scanline_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<mirror_MachineState>>(
+        WrapInfo {
+            debug_name: "step_emulator_scanline",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(step_emulator_scanline(api_id))
+        },
+    )
+}
+fn wire_step_emulator_frame_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<mirror_MachineState>>(
+        WrapInfo {
+            debug_name: "step_emulator_frame",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(step_emulator_frame(api_id))
+        },
+    )
+}
+fn wire_get_screen_buffer_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<u32>>>(
+        WrapInfo {
+            debug_name: "get_screen_buffer",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(get_screen_buffer(api_id))
+        },
+    )
+}
+fn wire_add_emulator_breakpoint_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    address: impl Wire2Api<u16> + UnwindSafe,
+    bank: impl Wire2Api<Option<u16>> + UnwindSafe,
+    condition: impl Wire2Api<Option<String>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, bool>(
+        WrapInfo {
+            debug_name: "add_emulator_breakpoint",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_address = address.wire2api();
+            let api_bank = bank.wire2api();
+            let api_condition = condition.wire2api();
+            move |task_callback| Ok(add_emulator_breakpoint(api_id, api_address, api_bank, api_condition))
+        },
+    )
+}
+fn wire_remove_emulator_breakpoint_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    address: impl Wire2Api<u16> + UnwindSafe,
+    bank: impl Wire2Api<Option<u16>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "remove_emulator_breakpoint",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_address = address.wire2api();
+            let api_bank = bank.wire2api();
+            move |task_callback| Ok(remove_emulator_breakpoint(api_id, api_address, api_bank))
+        },
+    )
+}
+fn wire_add_emulator_watchpoint_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    start: impl Wire2Api<u16> + UnwindSafe,
+    end: impl Wire2Api<u16> + UnwindSafe,
+    read: impl Wire2Api<bool> + UnwindSafe,
+    write: impl Wire2Api<bool> + UnwindSafe,
+    execute: impl Wire2Api<bool> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "add_emulator_watchpoint",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_start = start.wire2api();
+            let api_end = end.wire2api();
+            let api_read = read.wire2api();
+            let api_write = write.wire2api();
+            let api_execute = execute.wire2api();
+            move |task_callback| Ok(add_emulator_watchpoint(api_id, api_start, api_end, api_read, api_write, api_execute))
+        },
+    )
+}
+fn wire_remove_emulator_watchpoint_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    start: impl Wire2Api<u16> + UnwindSafe,
+    end: impl Wire2Api<u16> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "remove_emulator_watchpoint",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_start = start.wire2api();
+            let api_end = end.wire2api();
+            move |task_callback| Ok(remove_emulator_watchpoint(api_id, api_start, api_end))
+        },
+    )
+}
+fn wire_clear_emulator_breakpoints_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "clear_emulator_breakpoints",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(clear_emulator_breakpoints(api_id))
+        },
+    )
+}
+fn wire_set_emulator_break_on_interrupt_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    enabled: impl Wire2Api<bool> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "set_emulator_break_on_interrupt",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_enabled = enabled.wire2api();
+            move |task_callback| Ok(set_emulator_break_on_interrupt(api_id, api_enabled))
+        },
+    )
+}
+fn wire_get_stop_reason_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<mirror_StopReason>>(
+        WrapInfo {
+            debug_name: "get_stop_reason",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(get_stop_reason(api_id))
+        },
+    )
+}
+fn wire_resume_emulator_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "resume_emulator",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(resume_emulator(api_id))
+        },
+    )
+}
+fn wire_disassemble_memory_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    address: impl Wire2Api<u16> + UnwindSafe,
+    count: impl Wire2Api<u32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<mirror_Instruction>>>(
+        WrapInfo {
+            debug_name: "disassemble_memory",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_address = address.wire2api();
+            let api_count = count.wire2api();
+            move |task_callback| Ok(disassemble_memory(api_id, api_address, api_count))
+        },
+    )
+}
+fn wire_start_emulator_trace_file_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    path: impl Wire2Api<String> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, bool>(
+        WrapInfo {
+            debug_name: "start_emulator_trace_file",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_path = path.wire2api();
+            move |task_callback| Ok(start_emulator_trace_file(api_id, api_path))
+        },
+    )
+}
+fn wire_start_emulator_trace_buffer_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    capacity: impl Wire2Api<u32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "start_emulator_trace_buffer",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_capacity = capacity.wire2api();
+            move |task_callback| Ok(start_emulator_trace_buffer(api_id, api_capacity))
+        },
+    )
+}
+fn wire_stop_emulator_trace_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "stop_emulator_trace",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(stop_emulator_trace(api_id))
+        },
+    )
+}
+fn wire_get_emulator_trace_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<String>>(
+        WrapInfo {
+            debug_name: "get_emulator_trace",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(get_emulator_trace(api_id))
+        },
+    )
+}
+fn wire_start_gdb_server_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    port: impl Wire2Api<u16> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, bool>(
+        WrapInfo {
+            debug_name: "start_gdb_server",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_port = port.wire2api();
+            move |task_callback| Ok(start_gdb_server(api_id, api_port))
+        },
+    )
+}
+fn wire_stop_gdb_server_impl(port_: MessagePort) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "stop_gdb_server",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || move |task_callback| Ok(stop_gdb_server()),
+    )
+}
+fn wire_create_impl(
+    port_: MessagePort,
+    rom_data: impl Wire2Api<Vec<u8>> + UnwindSafe,
+    ram_data: impl Wire2Api<Option<Vec<u8>>> + UnwindSafe,
+    boot_rom: impl Wire2Api<Option<Vec<u8>>> + UnwindSafe,
+    model: impl Wire2Api<u8> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, u32>(
+        WrapInfo {
+            debug_name: "create",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_rom_data = rom_data.wire2api();
+            let api_ram_data = ram_data.wire2api();
+            let api_boot_rom = boot_rom.wire2api();
+            let api_model = model.wire2api();
+            move |task_callback| create(api_rom_data, api_ram_data, api_boot_rom, api_model)
+        },
+    )
+}
+fn wire_destroy_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<u8>>>(
+        WrapInfo {
+            debug_name: "destroy",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(destroy(api_id))
+        },
+    )
+}
+fn wire_render_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, mirror_FrameOutput>(
+        WrapInfo {
+            debug_name: "render",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| render(api_id)
+        },
+    )
+}
+fn wire_set_buttons_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    button_states: impl Wire2Api<Vec<u8>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "set_buttons",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_button_states = button_states.wire2api();
+            move |task_callback| Ok(set_buttons(api_id, api_button_states))
+        },
+    )
+}
+fn wire_set_sample_rate_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    sample_rate: impl Wire2Api<u32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "set_sample_rate",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_sample_rate = sample_rate.wire2api();
+            move |task_callback| Ok(set_sample_rate(api_id, api_sample_rate))
+        },
+    )
+}
+fn wire_drain_audio_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<i16>>>(
+        WrapInfo {
+            debug_name: "drain_audio",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(drain_audio(api_id))
+        },
+    )
+}
+fn wire_set_cgb_audio_filter_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    enabled: impl Wire2Api<bool> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "set_cgb_audio_filter",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_enabled = enabled.wire2api();
+            move |task_callback| Ok(set_cgb_audio_filter(api_id, api_enabled))
+        },
+    )
+}
+fn wire_mute_channel_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    channel: impl Wire2Api<u8> + UnwindSafe,
+    muted: impl Wire2Api<bool> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "mute_channel",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_channel = channel.wire2api();
+            let api_muted = muted.wire2api();
+            move |task_callback| Ok(mute_channel(api_id, api_channel, api_muted))
+        },
+    )
+}
+fn wire_solo_channel_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    channel: impl Wire2Api<u8> + UnwindSafe,
+    soloed: impl Wire2Api<bool> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "solo_channel",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_channel = channel.wire2api();
+            let api_soloed = soloed.wire2api();
+            move |task_callback| Ok(solo_channel(api_id, api_channel, api_soloed))
+        },
+    )
+}
+fn wire_channel_states_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<mirror_ChannelState>>>(
+        WrapInfo {
+            debug_name: "channel_states",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(channel_states(api_id))
+        },
+    )
+}
+fn wire_start_recording_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "start_recording",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(start_recording(api_id))
+        },
+    )
+}
+fn wire_stop_recording_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<u8>>>(
+        WrapInfo {
+            debug_name: "stop_recording",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(stop_recording(api_id))
+        },
+    )
+}
+fn wire_render_audio_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    sample_frames: impl Wire2Api<u32> + UnwindSafe,
+    buffer_fill: impl Wire2Api<f32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<mirror_AudioSyncOutput>>(
+        WrapInfo {
+            debug_name: "render_audio",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_sample_frames = sample_frames.wire2api();
+            let api_buffer_fill = buffer_fill.wire2api();
+            move |task_callback| Ok(render_audio(api_id, api_sample_frames, api_buffer_fill))
+        },
+    )
+}
+fn wire_save_state_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<u8>>>(
+        WrapInfo {
+            debug_name: "save_state",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(save_state(api_id))
+        },
+    )
+}
+fn wire_load_state_impl(
     port_: MessagePort,
-    rom_data: impl Wire2Api<Vec<u8>> + UnwindSafe,
-    ram_data: impl Wire2Api<Option<Vec<u8>>> + UnwindSafe,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    state: impl Wire2Api<Vec<u8>> + UnwindSafe,
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
         WrapInfo {
-            debug_name: "load_rom",
+            debug_name: "load_state",
             port: Some(port_),
             mode: FfiCallMode::Normal,
         },
         move || {
-            let api_rom_data = rom_data.wire2api();
-            let api_ram_data = ram_data.wire2api();
-            move |task_callback| Ok(load_rom(api_rom_data, api_ram_data))
+            let api_id = id.wire2api();
+            let api_state = state.wire2api();
+            move |task_callback| load_state(api_id, api_state)
         },
     )
 }
-fn wire_unload_emulator_impl(port_: MessagePort) {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<u8>>>(
+fn wire_configure_rewind_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    memory_budget: impl Wire2Api<u32> + UnwindSafe,
+    frames_per_snapshot: impl Wire2Api<u32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "configure_rewind",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_memory_budget = memory_budget.wire2api();
+            let api_frames_per_snapshot = frames_per_snapshot.wire2api();
+            move |task_callback| Ok(configure_rewind(api_id, api_memory_budget, api_frames_per_snapshot))
+        },
+    )
+}
+fn wire_rewind_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    frames: impl Wire2Api<u32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, u32>(
+        WrapInfo {
+            debug_name: "rewind",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_frames = frames.wire2api();
+            move |task_callback| Ok(rewind(api_id, api_frames))
+        },
+    )
+}
+fn wire_set_speed_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    multiplier: impl Wire2Api<f64> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "set_speed",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_multiplier = multiplier.wire2api();
+            move |task_callback| Ok(set_speed(api_id, api_multiplier))
+        },
+    )
+}
+fn wire_step_instruction_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<mirror_MachineState>>(
+        WrapInfo {
+            debug_name: "step_instruction",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(step_instruction(api_id))
+        },
+    )
+}
+fn wire_step_scanline_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<mirror_MachineState>>(
+        WrapInfo {
+            debug_name: "step_scanline",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(step_scanline(api_id))
+        },
+    )
+}
+fn wire_step_frame_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<mirror_MachineState>>(
         WrapInfo {
-            debug_name: "unload_emulator",
+            debug_name: "step_frame",
             port: Some(port_),
             mode: FfiCallMode::Normal,
         },
-        move || move |task_callback| Ok(unload_emulator()),
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(step_frame(api_id))
+        },
     )
 }
-fn wire_render_frame_impl(port_: MessagePort) {
+fn wire_current_frame_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<u32>>>(
         WrapInfo {
-            debug_name: "render_frame",
+            debug_name: "current_frame",
             port: Some(port_),
             mode: FfiCallMode::Normal,
         },
-        move || move |task_callback| Ok(render_frame()),
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(current_frame(api_id))
+        },
     )
 }
-fn wire_set_buttons_state_impl(
+fn wire_add_breakpoint_impl(
     port_: MessagePort,
-    button_states: impl Wire2Api<Vec<u8>> + UnwindSafe,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    address: impl Wire2Api<u16> + UnwindSafe,
+    bank: impl Wire2Api<Option<u16>> + UnwindSafe,
+    condition: impl Wire2Api<Option<String>> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, bool>(
+        WrapInfo {
+            debug_name: "add_breakpoint",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_address = address.wire2api();
+            let api_bank = bank.wire2api();
+            let api_condition = condition.wire2api();
+            move |task_callback| Ok(add_breakpoint(api_id, api_address, api_bank, api_condition))
+        },
+    )
+}
+fn wire_remove_breakpoint_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    address: impl Wire2Api<u16> + UnwindSafe,
+    bank: impl Wire2Api<Option<u16>> + UnwindSafe,
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
         WrapInfo {
-            debug_name: "set_buttons_state",
+            debug_name: "remove_breakpoint",
             port: Some(port_),
             mode: FfiCallMode::Normal,
         },
         move || {
-            let api_button_states = button_states.wire2api();
-            move |task_callback| Ok(set_buttons_state(api_button_states))
+            let api_id = id.wire2api();
+            let api_address = address.wire2api();
+            let api_bank = bank.wire2api();
+            move |task_callback| Ok(remove_breakpoint(api_id, api_address, api_bank))
         },
     )
 }
-fn wire_load_impl(
+fn wire_add_watchpoint_impl(
     port_: MessagePort,
-    rom_data: impl Wire2Api<Vec<u8>> + UnwindSafe,
-    ram_data: impl Wire2Api<Option<Vec<u8>>> + UnwindSafe,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    start: impl Wire2Api<u16> + UnwindSafe,
+    end: impl Wire2Api<u16> + UnwindSafe,
+    read: impl Wire2Api<bool> + UnwindSafe,
+    write: impl Wire2Api<bool> + UnwindSafe,
+    execute: impl Wire2Api<bool> + UnwindSafe,
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
         WrapInfo {
-            debug_name: "load",
+            debug_name: "add_watchpoint",
             port: Some(port_),
             mode: FfiCallMode::Normal,
         },
         move || {
-            let api_rom_data = rom_data.wire2api();
-            let api_ram_data = ram_data.wire2api();
-            move |task_callback| Ok(load(api_rom_data, api_ram_data))
+            let api_id = id.wire2api();
+            let api_start = start.wire2api();
+            let api_end = end.wire2api();
+            let api_read = read.wire2api();
+            let api_write = write.wire2api();
+            let api_execute = execute.wire2api();
+            move |task_callback| Ok(add_watchpoint(api_id, api_start, api_end, api_read, api_write, api_execute))
         },
     )
 }
-fn wire_unload_impl(port_: MessagePort) {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<u8>>>(
+fn wire_remove_watchpoint_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    start: impl Wire2Api<u16> + UnwindSafe,
+    end: impl Wire2Api<u16> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "remove_watchpoint",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_start = start.wire2api();
+            let api_end = end.wire2api();
+            move |task_callback| Ok(remove_watchpoint(api_id, api_start, api_end))
+        },
+    )
+}
+fn wire_clear_breakpoints_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
         WrapInfo {
-            debug_name: "unload",
+            debug_name: "clear_breakpoints",
             port: Some(port_),
             mode: FfiCallMode::Normal,
         },
-        move || move |task_callback| Ok(unload()),
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(clear_breakpoints(api_id))
+        },
     )
 }
-fn wire_render_impl(port_: MessagePort) {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<u32>>>(
+fn wire_break_on_interrupt_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    enabled: impl Wire2Api<bool> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
         WrapInfo {
-            debug_name: "render",
+            debug_name: "break_on_interrupt",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_enabled = enabled.wire2api();
+            move |task_callback| Ok(break_on_interrupt(api_id, api_enabled))
+        },
+    )
+}
+fn wire_stop_reason_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<mirror_StopReason>>(
+        WrapInfo {
+            debug_name: "stop_reason",
             port: Some(port_),
             mode: FfiCallMode::Normal,
         },
-        move || move |task_callback| Ok(render()),
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(stop_reason(api_id))
+        },
     )
 }
-fn wire_set_buttons_impl(port_: MessagePort, button_states: impl Wire2Api<Vec<u8>> + UnwindSafe) {
+fn wire_resume_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
         WrapInfo {
-            debug_name: "set_buttons",
+            debug_name: "resume",
             port: Some(port_),
             mode: FfiCallMode::Normal,
         },
         move || {
-            let api_button_states = button_states.wire2api();
-            move |task_callback| Ok(set_buttons(api_button_states))
+            let api_id = id.wire2api();
+            move |task_callback| Ok(resume(api_id))
+        },
+    )
+}
+fn wire_disassemble_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    address: impl Wire2Api<u16> + UnwindSafe,
+    count: impl Wire2Api<u32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<Vec<mirror_Instruction>>>(
+        WrapInfo {
+            debug_name: "disassemble",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_address = address.wire2api();
+            let api_count = count.wire2api();
+            move |task_callback| Ok(disassemble(api_id, api_address, api_count))
+        },
+    )
+}
+fn wire_trace_to_file_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    path: impl Wire2Api<String> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, bool>(
+        WrapInfo {
+            debug_name: "trace_to_file",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_path = path.wire2api();
+            move |task_callback| Ok(trace_to_file(api_id, api_path))
+        },
+    )
+}
+fn wire_trace_to_buffer_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    lines: impl Wire2Api<u32> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "trace_to_buffer",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_lines = lines.wire2api();
+            move |task_callback| Ok(trace_to_buffer(api_id, api_lines))
+        },
+    )
+}
+fn wire_stop_trace_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "stop_trace",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
         },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(stop_trace(api_id))
+        },
+    )
+}
+fn wire_trace_log_impl(port_: MessagePort, id: impl Wire2Api<u32> + UnwindSafe) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, Option<String>>(
+        WrapInfo {
+            debug_name: "trace_log",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            move |task_callback| Ok(trace_log(api_id))
+        },
+    )
+}
+fn wire_start_gdb_impl(
+    port_: MessagePort,
+    id: impl Wire2Api<u32> + UnwindSafe,
+    port: impl Wire2Api<u16> + UnwindSafe,
+) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, bool>(
+        WrapInfo {
+            debug_name: "start_gdb",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || {
+            let api_id = id.wire2api();
+            let api_port = port.wire2api();
+            move |task_callback| Ok(start_gdb(api_id, api_port))
+        },
+    )
+}
+fn wire_stop_gdb_impl(port_: MessagePort) {
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, ()>(
+        WrapInfo {
+            debug_name: "stop_gdb",
+            port: Some(port_),
+            mode: FfiCallMode::Normal,
+        },
+        move || move |task_callback| Ok(stop_gdb()),
     )
 }
 // Section: wrapper structs
 
+#[derive(Clone)]
+pub struct mirror_FrameOutput(FrameOutput);
+
+#[derive(Clone)]
+pub struct mirror_Instruction(Instruction);
+
+#[derive(Clone)]
+pub struct mirror_StopKind(StopKind);
+
+#[derive(Clone)]
+pub struct mirror_StopReason(StopReason);
+
+#[derive(Clone)]
+pub struct mirror_MachineState(MachineState);
+
+#[derive(Clone)]
+pub struct mirror_AudioSyncOutput(AudioSyncOutput);
+
+#[derive(Clone)]
+pub struct mirror_ChannelState(ChannelState);
+
 // Section: static checks
 
+const _: fn() = || {
+    {
+        let ChannelState = None::<ChannelState>.unwrap();
+        let _: bool = ChannelState.enabled;
+        let _: f64 = ChannelState.frequency_hz;
+        let _: u8 = ChannelState.volume;
+        let _: u8 = ChannelState.duty;
+        let _: u16 = ChannelState.length_remaining;
+    }
+    {
+        let AudioSyncOutput = None::<AudioSyncOutput>.unwrap();
+        let _: Vec<u32> = AudioSyncOutput.frame;
+        let _: Vec<i16> = AudioSyncOutput.samples;
+        let _: Option<StopReason> = AudioSyncOutput.stop_reason;
+    }
+    {
+        let MachineState = None::<MachineState>.unwrap();
+        let _: u8 = MachineState.a;
+        let _: u8 = MachineState.b;
+        let _: u8 = MachineState.c;
+        let _: u8 = MachineState.d;
+        let _: u8 = MachineState.e;
+        let _: u8 = MachineState.f;
+        let _: u8 = MachineState.h;
+        let _: u8 = MachineState.l;
+        let _: u16 = MachineState.pc;
+        let _: u16 = MachineState.sp;
+        let _: bool = MachineState.ime;
+        let _: bool = MachineState.halted;
+        let _: u8 = MachineState.ly;
+        let _: u8 = MachineState.ppu_mode;
+    }
+    {
+        let StopReason = None::<StopReason>.unwrap();
+        let _: StopKind = StopReason.kind;
+        let _: u16 = StopReason.address;
+    }
+    match None::<StopKind>.unwrap() {
+        StopKind::Breakpoint => {}
+        StopKind::ReadWatchpoint => {}
+        StopKind::WriteWatchpoint => {}
+        StopKind::ExecuteWatchpoint => {}
+        StopKind::Interrupt => {}
+    }
+    {
+        let Instruction = None::<Instruction>.unwrap();
+        let _: u16 = Instruction.address;
+        let _: Vec<u8> = Instruction.bytes;
+        let _: String = Instruction.text;
+        let _: u8 = Instruction.length;
+        let _: u8 = Instruction.cycles;
+        let _: u8 = Instruction.cycles_taken;
+    }
+    {
+        let FrameOutput = None::<FrameOutput>.unwrap();
+        let _: Vec<u32> = FrameOutput.frame;
+        let _: Option<StopReason> = FrameOutput.stop_reason;
+    }
+};
 // Section: allocate functions
 
 // Section: related functions
@@ -150,6 +1350,36 @@ where
     }
 }
 
+impl Wire2Api<bool> for bool {
+    fn wire2api(self) -> bool {
+        self
+    }
+}
+
+impl Wire2Api<f32> for f32 {
+    fn wire2api(self) -> f32 {
+        self
+    }
+}
+
+impl Wire2Api<f64> for f64 {
+    fn wire2api(self) -> f64 {
+        self
+    }
+}
+
+impl Wire2Api<u16> for u16 {
+    fn wire2api(self) -> u16 {
+        self
+    }
+}
+
+impl Wire2Api<u32> for u32 {
+    fn wire2api(self) -> u32 {
+        self
+    }
+}
+
 impl Wire2Api<u8> for u8 {
     fn wire2api(self) -> u8 {
         self
@@ -158,6 +1388,141 @@ impl Wire2Api<u8> for u8 {
 
 // Section: impl IntoDart
 
+impl support::IntoDart for mirror_FrameOutput {
+    fn into_dart(self) -> support::DartAbi {
+        vec![
+            self.0.frame.into_into_dart().into_dart(),
+            self.0.stop_reason.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for mirror_FrameOutput {}
+impl rust2dart::IntoIntoDart<mirror_FrameOutput> for FrameOutput {
+    fn into_into_dart(self) -> mirror_FrameOutput {
+        mirror_FrameOutput(self)
+    }
+}
+
+impl support::IntoDart for mirror_Instruction {
+    fn into_dart(self) -> support::DartAbi {
+        vec![
+            self.0.address.into_into_dart().into_dart(),
+            self.0.bytes.into_into_dart().into_dart(),
+            self.0.text.into_into_dart().into_dart(),
+            self.0.length.into_into_dart().into_dart(),
+            self.0.cycles.into_into_dart().into_dart(),
+            self.0.cycles_taken.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for mirror_Instruction {}
+impl rust2dart::IntoIntoDart<mirror_Instruction> for Instruction {
+    fn into_into_dart(self) -> mirror_Instruction {
+        mirror_Instruction(self)
+    }
+}
+
+impl support::IntoDart for mirror_StopKind {
+    fn into_dart(self) -> support::DartAbi {
+        match self.0 {
+            StopKind::Breakpoint => 0,
+            StopKind::ReadWatchpoint => 1,
+            StopKind::WriteWatchpoint => 2,
+            StopKind::ExecuteWatchpoint => 3,
+            StopKind::Interrupt => 4,
+        }
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for mirror_StopKind {}
+impl rust2dart::IntoIntoDart<mirror_StopKind> for StopKind {
+    fn into_into_dart(self) -> mirror_StopKind {
+        mirror_StopKind(self)
+    }
+}
+
+impl support::IntoDart for mirror_StopReason {
+    fn into_dart(self) -> support::DartAbi {
+        vec![
+            self.0.kind.into_into_dart().into_dart(),
+            self.0.address.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for mirror_StopReason {}
+impl rust2dart::IntoIntoDart<mirror_StopReason> for StopReason {
+    fn into_into_dart(self) -> mirror_StopReason {
+        mirror_StopReason(self)
+    }
+}
+
+impl support::IntoDart for mirror_MachineState {
+    fn into_dart(self) -> support::DartAbi {
+        vec![
+            self.0.a.into_into_dart().into_dart(),
+            self.0.b.into_into_dart().into_dart(),
+            self.0.c.into_into_dart().into_dart(),
+            self.0.d.into_into_dart().into_dart(),
+            self.0.e.into_into_dart().into_dart(),
+            self.0.f.into_into_dart().into_dart(),
+            self.0.h.into_into_dart().into_dart(),
+            self.0.l.into_into_dart().into_dart(),
+            self.0.pc.into_into_dart().into_dart(),
+            self.0.sp.into_into_dart().into_dart(),
+            self.0.ime.into_into_dart().into_dart(),
+            self.0.halted.into_into_dart().into_dart(),
+            self.0.ly.into_into_dart().into_dart(),
+            self.0.ppu_mode.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for mirror_MachineState {}
+impl rust2dart::IntoIntoDart<mirror_MachineState> for MachineState {
+    fn into_into_dart(self) -> mirror_MachineState {
+        mirror_MachineState(self)
+    }
+}
+
+impl support::IntoDart for mirror_AudioSyncOutput {
+    fn into_dart(self) -> support::DartAbi {
+        vec![
+            self.0.frame.into_into_dart().into_dart(),
+            self.0.samples.into_into_dart().into_dart(),
+            self.0.stop_reason.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for mirror_AudioSyncOutput {}
+impl rust2dart::IntoIntoDart<mirror_AudioSyncOutput> for AudioSyncOutput {
+    fn into_into_dart(self) -> mirror_AudioSyncOutput {
+        mirror_AudioSyncOutput(self)
+    }
+}
+
+impl support::IntoDart for mirror_ChannelState {
+    fn into_dart(self) -> support::DartAbi {
+        vec![
+            self.0.enabled.into_into_dart().into_dart(),
+            self.0.frequency_hz.into_into_dart().into_dart(),
+            self.0.volume.into_into_dart().into_dart(),
+            self.0.duty.into_into_dart().into_dart(),
+            self.0.length_remaining.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for mirror_ChannelState {}
+impl rust2dart::IntoIntoDart<mirror_ChannelState> for ChannelState {
+    fn into_into_dart(self) -> mirror_ChannelState {
+        mirror_ChannelState(self)
+    }
+}
+
 // Section: executor
 
 support::lazy_static! {
@@ -1,3 +1,5 @@
+use crate::error::{CoreError, MIN_ROM_SIZE};
+
 pub struct CARTRIDGE {
     pub rom: Vec<u8>,                // Cała zawartość ROM
     pub entry_point: [u8; 4],        // 0x0100-0x0103
@@ -19,7 +21,11 @@ pub struct CARTRIDGE {
 }
 
 impl CARTRIDGE {
-    pub fn new(data: Vec<u8>, saved_ram: Option<Vec<u8>>) -> Self {
+    pub fn new(data: Vec<u8>, mut saved_ram: Option<Vec<u8>>) -> Result<Self, CoreError> {
+        if data.len() < MIN_ROM_SIZE {
+            return Err(CoreError::RomTooSmall(data.len()));
+        }
+
         let ram_size = match data.get(0x0149) {
             Some(0x00) => None,             
             Some(0x01) => Some(2 * 1024),   
@@ -29,8 +35,13 @@ impl CARTRIDGE {
             Some(0x05) => Some(64 * 1024),  
             _ => None,                      
         };
+        // Saves from other emulators may carry extra data, like the MBC3 clock stored after the RAM.
+        // Only the RAM part is kept, and a short save is padded with zeros.
+        if let (Some(size), Some(ref mut ram)) = (ram_size, &mut saved_ram) {
+            ram.resize(size as usize, 0);
+        }
 
-        Self {
+        Ok(Self {
             entry_point: [data[0x0100], data[0x0101], data[0x0102], data[0x0103]],
             nintendo_logo: data[0x0104..0x0134].try_into().unwrap(),
             title: data[0x0134..0x0143].try_into().unwrap(),
@@ -48,6 +59,6 @@ impl CARTRIDGE {
             global_checksum: [data[0x014E], data[0x014F]],
             rom: data,
             saved_ram,
-        }
+        })
     }
 }
@@ -0,0 +1,248 @@
+const REGISTERS: [&str; 8] = ["B", "C", "D", "E", "H", "L", "(HL)", "A"];
+const REGISTER_PAIRS: [&str; 4] = ["BC", "DE", "HL", "SP"];
+const STACK_REGISTER_PAIRS: [&str; 4] = ["BC", "DE", "HL", "AF"];
+const CONDITIONS: [&str; 4] = ["NZ", "Z", "NC", "C"];
+const ALU_OPERATIONS: [&str; 8] = ["ADD A,", "ADC A,", "SUB", "SBC A,", "AND", "XOR", "OR", "CP"];
+const ROTATIONS: [&str; 8] = ["RLC", "RRC", "RL", "RR", "SLA", "SRA", "SWAP", "SRL"];
+const ACCUMULATOR_OPERATIONS: [&str; 8] = ["RLCA", "RRCA", "RLA", "RRA", "DAA", "CPL", "SCF", "CCF"];
+
+#[derive(Clone)]
+pub struct Instruction {
+    pub address: u16,
+    pub bytes: Vec<u8>,   // Opcode followed by its operands
+    pub text: String,     // Mnemonic with operands, e.g. "LD A, ($C000)"
+    pub length: u8,
+    pub cycles: u8,       // Clock cycles, for conditional instructions when the condition fails
+    pub cycles_taken: u8, // Clock cycles when a conditional jump, call or return is taken
+}
+
+// Decodes the instruction at `address`, reading memory through `read`.
+pub fn decode(address: u16, read: impl Fn(u16) -> u8) -> Instruction {
+    let opcode = read(address);
+    let d8 = read(address.wrapping_add(1));
+    let d16 = u16::from_le_bytes([d8, read(address.wrapping_add(2))]);
+
+    let (text, length, cycles, cycles_taken) = if opcode == 0xCB {
+        let (text, cycles) = decode_prefixed(d8);
+        (text, 2, cycles, cycles)
+    } else {
+        decode_not_prefixed(address, opcode, d8, d16)
+    };
+
+    Instruction {
+        address,
+        bytes: (0..length).map(|offset| read(address.wrapping_add(offset as u16))).collect(),
+        text,
+        length,
+        cycles,
+        cycles_taken,
+    }
+}
+
+fn decode_not_prefixed(address: u16, opcode: u8, d8: u8, d16: u16) -> (String, u8, u8, u8) {
+    let bits_7_6 = opcode >> 6;
+    let bits_5_4_3 = ((opcode >> 3) & 0b111) as usize;
+    let bits_2_1_0 = (opcode & 0b111) as usize;
+    let pair = bits_5_4_3 >> 1;
+    let relative_target = address.wrapping_add(2).wrapping_add(d8 as i8 as u16);
+    let register_cycles = |register: usize, cycles: u8, memory_cycles: u8| {
+        if register == 6 { memory_cycles } else { cycles }
+    };
+
+    let fixed = |text: String, length: u8, cycles: u8| (text, length, cycles, cycles);
+
+    match bits_7_6 {
+        0 => match bits_2_1_0 {
+            0 => match bits_5_4_3 {
+                0 => fixed("NOP".to_string(), 1, 4),
+                1 => fixed(format!("LD (${:04X}), SP", d16), 3, 20),
+                2 => fixed("STOP".to_string(), 2, 4),
+                3 => fixed(format!("JR ${:04X}", relative_target), 2, 12),
+                _ => (format!("JR {}, ${:04X}", CONDITIONS[bits_5_4_3 - 4], relative_target), 2, 8, 12),
+            },
+            1 => {
+                if bits_5_4_3 & 0x01 == 0 {
+                    fixed(format!("LD {}, ${:04X}", REGISTER_PAIRS[pair], d16), 3, 12)
+                } else {
+                    fixed(format!("ADD HL, {}", REGISTER_PAIRS[pair]), 1, 8)
+                }
+            },
+            2 => {
+                let memory = ["(BC)", "(DE)", "(HL+)", "(HL-)"][pair];
+                if bits_5_4_3 & 0x01 == 0 {
+                    fixed(format!("LD {}, A", memory), 1, 8)
+                } else {
+                    fixed(format!("LD A, {}", memory), 1, 8)
+                }
+            },
+            3 => {
+                let operation = if bits_5_4_3 & 0x01 == 0 { "INC" } else { "DEC" };
+                fixed(format!("{} {}", operation, REGISTER_PAIRS[pair]), 1, 8)
+            },
+            4 => fixed(format!("INC {}", REGISTERS[bits_5_4_3]), 1, register_cycles(bits_5_4_3, 4, 12)),
+            5 => fixed(format!("DEC {}", REGISTERS[bits_5_4_3]), 1, register_cycles(bits_5_4_3, 4, 12)),
+            6 => fixed(format!("LD {}, ${:02X}", REGISTERS[bits_5_4_3], d8), 2, register_cycles(bits_5_4_3, 8, 12)),
+            7 => fixed(ACCUMULATOR_OPERATIONS[bits_5_4_3].to_string(), 1, 4),
+            _ => unreachable!(),
+        },
+        1 => {
+            if bits_5_4_3 == 6 && bits_2_1_0 == 6 {
+                fixed("HALT".to_string(), 1, 4)
+            } else {
+                let cycles = if bits_5_4_3 == 6 || bits_2_1_0 == 6 { 8 } else { 4 };
+                fixed(format!("LD {}, {}", REGISTERS[bits_5_4_3], REGISTERS[bits_2_1_0]), 1, cycles)
+            }
+        },
+        2 => {
+            let text = format!("{} {}", ALU_OPERATIONS[bits_5_4_3], REGISTERS[bits_2_1_0]);
+            fixed(text, 1, register_cycles(bits_2_1_0, 4, 8))
+        },
+        3 => match bits_2_1_0 {
+            0 => match bits_5_4_3 {
+                0..=3 => (format!("RET {}", CONDITIONS[bits_5_4_3]), 1, 8, 20),
+                4 => fixed(format!("LDH (${:04X}), A", 0xFF00 | d8 as u16), 2, 12),
+                5 => fixed(format!("ADD SP, {}", d8 as i8), 2, 16),
+                6 => fixed(format!("LDH A, (${:04X})", 0xFF00 | d8 as u16), 2, 12),
+                7 => fixed(format!("LD HL, SP{:+}", d8 as i8), 2, 12),
+                _ => unreachable!(),
+            },
+            1 => {
+                if bits_5_4_3 & 0x01 == 0 {
+                    fixed(format!("POP {}", STACK_REGISTER_PAIRS[pair]), 1, 12)
+                } else {
+                    match pair {
+                        0 => fixed("RET".to_string(), 1, 16),
+                        1 => fixed("RETI".to_string(), 1, 16),
+                        2 => fixed("JP HL".to_string(), 1, 4),
+                        3 => fixed("LD SP, HL".to_string(), 1, 8),
+                        _ => unreachable!(),
+                    }
+                }
+            },
+            2 => match bits_5_4_3 {
+                0..=3 => (format!("JP {}, ${:04X}", CONDITIONS[bits_5_4_3], d16), 3, 12, 16),
+                4 => fixed("LD ($FF00+C), A".to_string(), 1, 8),
+                5 => fixed(format!("LD (${:04X}), A", d16), 3, 16),
+                6 => fixed("LD A, ($FF00+C)".to_string(), 1, 8),
+                7 => fixed(format!("LD A, (${:04X})", d16), 3, 16),
+                _ => unreachable!(),
+            },
+            3 => match bits_5_4_3 {
+                0 => fixed(format!("JP ${:04X}", d16), 3, 16),
+                6 => fixed("DI".to_string(), 1, 4),
+                7 => fixed("EI".to_string(), 1, 4),
+                _ => illegal(opcode),
+            },
+            4 => match bits_5_4_3 {
+                0..=3 => (format!("CALL {}, ${:04X}", CONDITIONS[bits_5_4_3], d16), 3, 12, 24),
+                _ => illegal(opcode),
+            },
+            5 => {
+                if bits_5_4_3 & 0x01 == 0 {
+                    fixed(format!("PUSH {}", STACK_REGISTER_PAIRS[pair]), 1, 16)
+                } else if pair == 0 {
+                    fixed(format!("CALL ${:04X}", d16), 3, 24)
+                } else {
+                    illegal(opcode)
+                }
+            },
+            6 => fixed(format!("{} ${:02X}", ALU_OPERATIONS[bits_5_4_3], d8), 2, 8),
+            7 => fixed(format!("RST ${:02X}", bits_5_4_3 * 8), 1, 16),
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    }
+}
+
+// Opcodes that lock up the CPU on real hardware.
+fn illegal(opcode: u8) -> (String, u8, u8, u8) {
+    (format!("DB ${:02X}", opcode), 1, 4, 4)
+}
+
+fn decode_prefixed(opcode: u8) -> (String, u8) {
+    let bits_7_6 = opcode >> 6;
+    let bits_5_4_3 = ((opcode >> 3) & 0b111) as usize;
+    let register = REGISTERS[(opcode & 0b111) as usize];
+    let memory = opcode & 0b111 == 6;
+
+    match bits_7_6 {
+        0 => (format!("{} {}", ROTATIONS[bits_5_4_3], register), if memory { 16 } else { 8 }),
+        1 => (format!("BIT {}, {}", bits_5_4_3, register), if memory { 12 } else { 8 }),
+        2 => (format!("RES {}, {}", bits_5_4_3, register), if memory { 16 } else { 8 }),
+        3 => (format!("SET {}, {}", bits_5_4_3, register), if memory { 16 } else { 8 }),
+        _ => unreachable!(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: u16 = 0x0150;
+
+    fn decode_bytes(bytes: &[u8]) -> Instruction {
+        decode(BASE, |address| bytes.get(address.wrapping_sub(BASE) as usize).copied().unwrap_or(0))
+    }
+
+    fn assert_decodes(bytes: &[u8], text: &str, cycles: u8, cycles_taken: u8) {
+        let instruction = decode_bytes(bytes);
+        assert_eq!(instruction.text, text);
+        assert_eq!(instruction.bytes, bytes);
+        assert_eq!(instruction.length as usize, bytes.len());
+        assert_eq!((instruction.cycles, instruction.cycles_taken), (cycles, cycles_taken));
+        assert_eq!(instruction.address, BASE);
+    }
+
+    #[test]
+    fn decodes_instructions_with_operands() {
+        assert_decodes(&[0x00], "NOP", 4, 4);
+        assert_decodes(&[0x01, 0x34, 0x12], "LD BC, $1234", 12, 12);
+        assert_decodes(&[0x08, 0x00, 0xC0], "LD ($C000), SP", 20, 20);
+        assert_decodes(&[0x10, 0x00], "STOP", 4, 4);
+        assert_decodes(&[0x36, 0x7F], "LD (HL), $7F", 12, 12);
+        assert_decodes(&[0x7E], "LD A, (HL)", 8, 8);
+        assert_decodes(&[0x86], "ADD A, (HL)", 8, 8);
+        assert_decodes(&[0xE0, 0x40], "LDH ($FF40), A", 12, 12);
+        assert_decodes(&[0xE8, 0xFE], "ADD SP, -2", 16, 16);
+        assert_decodes(&[0xF8, 0x02], "LD HL, SP+2", 12, 12);
+        assert_decodes(&[0xFF], "RST $38", 16, 16);
+    }
+
+    #[test]
+    fn relative_jumps_show_the_target() {
+        assert_decodes(&[0x18, 0xFE], "JR $0150", 12, 12);
+        assert_decodes(&[0x20, 0x05], "JR NZ, $0157", 8, 12);
+    }
+
+    #[test]
+    fn conditional_instructions_have_two_timings() {
+        assert_decodes(&[0xC0], "RET NZ", 8, 20);
+        assert_decodes(&[0xC2, 0x50, 0x01], "JP NZ, $0150", 12, 16);
+        assert_decodes(&[0xC4, 0x00, 0x40], "CALL NZ, $4000", 12, 24);
+        assert_decodes(&[0xCD, 0x00, 0x40], "CALL $4000", 24, 24);
+    }
+
+    #[test]
+    fn decodes_prefixed_instructions() {
+        assert_decodes(&[0xCB, 0x37], "SWAP A", 8, 8);
+        assert_decodes(&[0xCB, 0x7C], "BIT 7, H", 8, 8);
+        assert_decodes(&[0xCB, 0x46], "BIT 0, (HL)", 12, 12);
+        assert_decodes(&[0xCB, 0xFE], "SET 7, (HL)", 16, 16);
+    }
+
+    #[test]
+    fn illegal_opcodes_decode_as_data() {
+        for opcode in [0xD3, 0xDB, 0xDD, 0xE3, 0xE4, 0xEB, 0xEC, 0xED, 0xF4, 0xFC, 0xFD] {
+            assert_decodes(&[opcode], &format!("DB ${:02X}", opcode), 4, 4);
+        }
+    }
+
+    #[test]
+    fn every_opcode_decodes() {
+        for opcode in 0..=0xFF {
+            let instruction = decode_bytes(&[opcode, 0x00, 0x00]);
+            assert!((1..=3).contains(&instruction.length));
+            assert!(instruction.cycles <= instruction.cycles_taken);
+        }
+    }
+}
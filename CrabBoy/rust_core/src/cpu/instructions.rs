@@ -7,12 +7,32 @@ pub fn nop(cpu: &mut CPU) {
 
 pub fn stop(cpu: &mut CPU) {
     cpu.set_cycles(4);
-    cpu.pc += 1;
+    cpu.pc += 2; // STOP is followed by a padding byte
+    let mmu = &mut cpu.mmu;
+    mmu.reset_divider();
+    // On CGB an armed KEY1 turns STOP into a speed switch instead of entering STOP mode.
+    if mmu.is_speed_switch_armed() {
+        mmu.switch_speed();
+    } else {
+        cpu.stopped = true;
+    }
 }
 
 pub fn halt(cpu: &mut CPU) {
     cpu.set_cycles(4);
-    cpu.halted = true;
+    cpu.pc += 1;
+    // With IME off and an interrupt already pending HALT is skipped, triggering the HALT bug.
+    if !cpu.ime && cpu.pending_interrupts() != 0 {
+        cpu.halt_bug = true;
+    } else {
+        cpu.halted = true;
+    }
+}
+
+// The unused opcodes hang the CPU instead of executing anything.
+pub fn lock(cpu: &mut CPU) {
+    cpu.set_cycles(4);
+    cpu.locked = true;
 }
 
 pub fn inc_r_u8(cpu: &mut CPU, register: u8) -> u8 {
@@ -71,7 +91,7 @@ pub fn inc_m_hl(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let (half_carry, zero) = {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         let value = mmu.read_byte(address);
         let half_carry = (value & 0x0F) == 0x0F;
         let result = value.wrapping_add(1);
@@ -91,7 +111,7 @@ pub fn dec_m_hl(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let (half_carry, zero) = {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         let value = mmu.read_byte(address);
         let half_carry = (value & 0x0F) == 0x00;
         let result = value.wrapping_sub(1);
@@ -145,7 +165,7 @@ pub fn add_a_m_hl(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
     
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.read_byte(address)
     };
     
@@ -161,7 +181,7 @@ pub fn add_a_m_hl(cpu: &mut CPU) {
 
 pub fn add_a_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
     
@@ -177,7 +197,7 @@ pub fn add_a_u8(cpu: &mut CPU) {
 
 pub fn add_sp_i8(cpu: &mut CPU) {
     let offset = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_i8(cpu.pc + 1) as i16 as u16
     };
 
@@ -209,7 +229,7 @@ pub fn adc_a_r_u8(cpu: &mut CPU, register: u8) {
 pub fn adc_a_m_hl(cpu: &mut CPU) {
     let value = {
         let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.read_byte(address)
     };
 
@@ -228,7 +248,7 @@ pub fn adc_a_m_hl(cpu: &mut CPU) {
 
 pub fn adc_a_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
@@ -257,7 +277,7 @@ pub fn sub_a_r_u8(cpu: &mut CPU, register: u8) {
 
 pub fn sub_a_m_hl(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.read_byte(((cpu.h as u16) << 8) | (cpu.l as u16))
     };
 
@@ -273,7 +293,7 @@ pub fn sub_a_m_hl(cpu: &mut CPU) {
 
 pub fn sub_a_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
@@ -302,7 +322,7 @@ pub fn sbc_a_r_u8(cpu: &mut CPU, register: u8) {
 
 pub fn sbc_a_m_hl(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.read_byte(((cpu.h as u16) << 8) | (cpu.l as u16))
     };
 
@@ -321,7 +341,7 @@ pub fn sbc_a_m_hl(cpu: &mut CPU) {
 
 pub fn sbc_a_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
@@ -349,7 +369,7 @@ pub fn and_a_r_u8(cpu: &mut CPU, register: u8) {
 
 pub fn and_a_m_hl(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.read_byte(((cpu.h as u16) << 8) | (cpu.l as u16))
     };
 
@@ -363,7 +383,7 @@ pub fn and_a_m_hl(cpu: &mut CPU) {
 
 pub fn and_a_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
@@ -385,7 +405,7 @@ pub fn xor_a_r_u8(cpu: &mut CPU, register: u8) {
 
 pub fn xor_a_m_hl(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.read_byte(((cpu.h as u16) << 8) | (cpu.l as u16))
     };
 
@@ -398,7 +418,7 @@ pub fn xor_a_m_hl(cpu: &mut CPU) {
 
 pub fn xor_a_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
@@ -419,7 +439,7 @@ pub fn or_a_r_u8(cpu: &mut CPU, register: u8) {
 
 pub fn or_a_m_hl(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.read_byte(((cpu.h as u16) << 8) | (cpu.l as u16))
     };
 
@@ -432,7 +452,7 @@ pub fn or_a_m_hl(cpu: &mut CPU) {
 
 pub fn or_a_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
@@ -454,7 +474,7 @@ pub fn cp_a_r_u8(cpu: &mut CPU, register: u8) {
 
 pub fn cp_a_m_hl(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.read_byte(((cpu.h as u16) << 8) | (cpu.l as u16))
     };
 
@@ -468,7 +488,7 @@ pub fn cp_a_m_hl(cpu: &mut CPU) {
 
 pub fn cp_a_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
@@ -524,7 +544,7 @@ pub fn ld_a_r_u8(cpu: &mut CPU, register: u8) {
 
 pub fn ld_b_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
     cpu.b = value;
@@ -534,7 +554,7 @@ pub fn ld_b_u8(cpu: &mut CPU) {
 
 pub fn ld_c_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
@@ -545,7 +565,7 @@ pub fn ld_c_u8(cpu: &mut CPU) {
 
 pub fn ld_d_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
@@ -556,7 +576,7 @@ pub fn ld_d_u8(cpu: &mut CPU) {
 
 pub fn ld_e_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
@@ -567,7 +587,7 @@ pub fn ld_e_u8(cpu: &mut CPU) {
 
 pub fn ld_h_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
@@ -578,7 +598,7 @@ pub fn ld_h_u8(cpu: &mut CPU) {
 
 pub fn ld_l_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
@@ -589,7 +609,7 @@ pub fn ld_l_u8(cpu: &mut CPU) {
 
 pub fn ld_a_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
@@ -600,14 +620,14 @@ pub fn ld_a_u8(cpu: &mut CPU) {
 
 pub fn ld_m_hl_u8(cpu: &mut CPU) {
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_u8(cpu.pc + 1)
     };
 
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.write_byte(address, value);
     }
 
@@ -617,7 +637,7 @@ pub fn ld_m_hl_u8(cpu: &mut CPU) {
 
 pub fn ld_bc_u16(cpu: &mut CPU) {
     let (c, b) = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         (mmu.fetch_u8(cpu.pc + 1), mmu.fetch_u8(cpu.pc + 2))
     };
 
@@ -629,7 +649,7 @@ pub fn ld_bc_u16(cpu: &mut CPU) {
 
 pub fn ld_de_u16(cpu: &mut CPU) {
     let (e, d) = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         (mmu.fetch_u8(cpu.pc + 1), mmu.fetch_u8(cpu.pc + 2))
     };
 
@@ -641,7 +661,7 @@ pub fn ld_de_u16(cpu: &mut CPU) {
 
 pub fn ld_hl_u16(cpu: &mut CPU) {
     let (l, h) = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         (mmu.fetch_u8(cpu.pc + 1), mmu.fetch_u8(cpu.pc + 2))
     };
 
@@ -653,10 +673,8 @@ pub fn ld_hl_u16(cpu: &mut CPU) {
 
 pub fn ld_sp_u16(cpu: &mut CPU) {
     let sp = {
-        let mmu = cpu.mmu.lock().unwrap();
-        let high_byte = mmu.fetch_u8(cpu.pc + 2) as u16;
-        let low_byte = mmu.fetch_u8(cpu.pc + 1) as u16;
-        (high_byte << 8) | low_byte
+        let mmu = &mut cpu.mmu;
+        mmu.fetch_u16(cpu.pc + 1)
     };
 
     cpu.sp = sp;
@@ -668,7 +686,7 @@ pub fn ld_m_r_u16_a(cpu: &mut CPU, register1: u8, register2: u8) {
     let address = ((register1 as u16) << 8) | (register2 as u16);
     
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.write_byte(address, cpu.a);
     }
 
@@ -680,7 +698,7 @@ pub fn ld_a_m_r_u16(cpu: &mut CPU, register1: u8, register2: u8) {
     let address = ((register1 as u16) << 8) | (register2 as u16);
     
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.read_byte(address)
     };
 
@@ -693,7 +711,7 @@ pub fn ld_a_hl_inc(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
     
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.read_byte(address)
     };
     
@@ -709,7 +727,7 @@ pub fn ld_hl_inc_a(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.write_byte(address, cpu.a);
     }
 
@@ -724,7 +742,7 @@ pub fn ld_a_hl_dec(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.read_byte(address)
     };
     
@@ -740,7 +758,7 @@ pub fn ld_hl_dec_a(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.write_byte(address, cpu.a);
     }
 
@@ -755,7 +773,7 @@ pub fn ld_m_hl_r_u8(cpu: &mut CPU, register: u8) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.write_byte(address, register);
     }
 
@@ -767,7 +785,7 @@ pub fn ld_r_u8_m_hl(cpu: &mut CPU) -> u8 {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let value = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.read_byte(address)
     };
 
@@ -780,15 +798,15 @@ pub fn ld_r_u8_m_hl(cpu: &mut CPU) -> u8 {
 
 pub fn ld_m_u16_sp(cpu: &mut CPU) {
     let address = {
-        let mmu = cpu.mmu.lock().unwrap();
-        ((mmu.fetch_u8(cpu.pc + 2) as u16) << 8) | (mmu.fetch_u8(cpu.pc + 1) as u16)
+        let mmu = &mut cpu.mmu;
+        mmu.fetch_u16(cpu.pc + 1)
     };
 
     let lower_byte = (cpu.sp & 0xFF) as u8;
     let upper_byte = (cpu.sp >> 8) as u8;
 
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.write_byte(address, lower_byte);
         mmu.write_byte(address + 1, upper_byte);
     }
@@ -805,21 +823,21 @@ pub fn ld_sp_hl(cpu: &mut CPU) {
 
 pub fn ld_ff00_plus_u8_a(cpu: &mut CPU) {
     let address = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         0xFF00 + mmu.fetch_u8(cpu.pc + 1) as u16
     };
     
-    cpu.mmu.lock().unwrap().write_byte(address, cpu.a);
+    cpu.mmu.write_byte(address, cpu.a);
     cpu.set_cycles(12);
     cpu.pc += 2;
 }
 
 pub fn ld_a_ff00_plus_u8(cpu: &mut CPU) {
     let address = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         0xFF00 + mmu.fetch_u8(cpu.pc + 1) as u16
     };
-    cpu.a = cpu.mmu.lock().unwrap().read_byte(address);
+    cpu.a = cpu.mmu.read_byte(address);
     cpu.set_cycles(12);
     cpu.pc += 2;
 }
@@ -827,7 +845,7 @@ pub fn ld_a_ff00_plus_u8(cpu: &mut CPU) {
 pub fn ld_ff00_plus_c_a(cpu: &mut CPU) {
     let address = 0xFF00 + cpu.c as u16;
     
-    cpu.mmu.lock().unwrap().write_byte(address, cpu.a);
+    cpu.mmu.write_byte(address, cpu.a);
     cpu.set_cycles(8);
     cpu.pc += 1;
 }
@@ -835,7 +853,7 @@ pub fn ld_ff00_plus_c_a(cpu: &mut CPU) {
 pub fn ld_a_ff00_plus_c(cpu: &mut CPU) {
     let address = 0xFF00 + cpu.c as u16;
     
-    cpu.a = cpu.mmu.lock().unwrap().read_byte(address);
+    cpu.a = cpu.mmu.read_byte(address);
     cpu.set_cycles(8);
     cpu.pc += 1;
 }
@@ -843,29 +861,29 @@ pub fn ld_a_ff00_plus_c(cpu: &mut CPU) {
 
 pub fn ld_a_u16(cpu: &mut CPU) {
     let address = {
-        let mmu = cpu.mmu.lock().unwrap();
-        ((mmu.fetch_u8(cpu.pc + 2) as u16) << 8) | (mmu.fetch_u8(cpu.pc + 1) as u16)
+        let mmu = &mut cpu.mmu;
+        mmu.fetch_u16(cpu.pc + 1)
     };
 
-    cpu.a = cpu.mmu.lock().unwrap().read_byte(address);
+    cpu.a = cpu.mmu.read_byte(address);
     cpu.set_cycles(16);
     cpu.pc += 3;
 }
 
 pub fn ld_u16_a(cpu: &mut CPU) {
     let address = {
-        let mmu = cpu.mmu.lock().unwrap();
-        ((mmu.fetch_u8(cpu.pc + 2) as u16) << 8) | (mmu.fetch_u8(cpu.pc + 1) as u16)
+        let mmu = &mut cpu.mmu;
+        mmu.fetch_u16(cpu.pc + 1)
     };
 
-    cpu.mmu.lock().unwrap().write_byte(address, cpu.a);
+    cpu.mmu.write_byte(address, cpu.a);
     cpu.set_cycles(16);
     cpu.pc += 3;
 }
 
 pub fn ld_hl_sp_plus_i8(cpu: &mut CPU) {
     let offset = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_i8(cpu.pc + 1) as i16 as u16
     };
     
@@ -883,7 +901,7 @@ pub fn ld_hl_sp_plus_i8(cpu: &mut CPU) {
 
 pub fn jr_i8(cpu: &mut CPU) {
     let offset = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_i8(cpu.pc + 1)
     };
     let signed_value = offset as i16 as u16;
@@ -893,7 +911,7 @@ pub fn jr_i8(cpu: &mut CPU) {
 
 pub fn jr_nz_i8(cpu: &mut CPU) {
     let offset = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_i8(cpu.pc + 1)
     };
     let signed_value = offset as i16 as u16;
@@ -908,7 +926,7 @@ pub fn jr_nz_i8(cpu: &mut CPU) {
 
 pub fn jr_z_i8(cpu: &mut CPU) {
     let offset = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_i8(cpu.pc + 1)
     };
     let signed_value = offset as i16 as u16;
@@ -923,7 +941,7 @@ pub fn jr_z_i8(cpu: &mut CPU) {
 
 pub fn jr_nc_i8(cpu: &mut CPU) {
     let offset = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_i8(cpu.pc + 1)
     };
     let signed_value = offset as i16 as u16;
@@ -938,7 +956,7 @@ pub fn jr_nc_i8(cpu: &mut CPU) {
 
 pub fn jr_c_i8(cpu: &mut CPU) {
     let offset = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.fetch_i8(cpu.pc + 1)
     };
     let signed_value = offset as i16 as u16;
@@ -958,8 +976,8 @@ pub fn jp_hl(cpu: &mut CPU) {
 
 pub fn jp_u16(cpu: &mut CPU) {
     let address = {
-        let mmu = cpu.mmu.lock().unwrap();
-        ((mmu.fetch_u8(cpu.pc + 2) as u16) << 8) | (mmu.fetch_u8(cpu.pc + 1) as u16)
+        let mmu = &mut cpu.mmu;
+        mmu.fetch_u16(cpu.pc + 1)
     };
     cpu.set_cycles(16);
     cpu.pc = address;
@@ -968,8 +986,8 @@ pub fn jp_u16(cpu: &mut CPU) {
 pub fn jp_nz_u16(cpu: &mut CPU) {
     if (cpu.f & 0x80) >> 7 != 1 {
         let address = {
-            let mmu = cpu.mmu.lock().unwrap();
-            ((mmu.fetch_u8(cpu.pc + 2) as u16) << 8) | (mmu.fetch_u8(cpu.pc + 1) as u16)
+            let mmu = &mut cpu.mmu;
+            mmu.fetch_u16(cpu.pc + 1)
         };
         cpu.set_cycles(16);
         cpu.pc = address;
@@ -982,8 +1000,8 @@ pub fn jp_nz_u16(cpu: &mut CPU) {
 pub fn jp_z_u16(cpu: &mut CPU) {
     if (cpu.f & 0x80) >> 7 == 1 {
         let address = {
-            let mmu = cpu.mmu.lock().unwrap();
-            ((mmu.fetch_u8(cpu.pc + 2) as u16) << 8) | (mmu.fetch_u8(cpu.pc + 1) as u16)
+            let mmu = &mut cpu.mmu;
+            mmu.fetch_u16(cpu.pc + 1)
         };
         cpu.set_cycles(16);
         cpu.pc = address;
@@ -996,8 +1014,8 @@ pub fn jp_z_u16(cpu: &mut CPU) {
 pub fn jp_nc_u16(cpu: &mut CPU) {
     if (cpu.f & 0x10) >> 4 != 1 {
         let address = {
-            let mmu = cpu.mmu.lock().unwrap();
-            ((mmu.fetch_u8(cpu.pc + 2) as u16) << 8) | (mmu.fetch_u8(cpu.pc + 1) as u16)
+            let mmu = &mut cpu.mmu;
+            mmu.fetch_u16(cpu.pc + 1)
         };
         cpu.set_cycles(16);
         cpu.pc = address;
@@ -1010,8 +1028,8 @@ pub fn jp_nc_u16(cpu: &mut CPU) {
 pub fn jp_c_u16(cpu: &mut CPU) {
     if (cpu.f & 0x10) >> 4 == 1 {
         let address = {
-            let mmu = cpu.mmu.lock().unwrap();
-            ((mmu.fetch_u8(cpu.pc + 2) as u16) << 8) | (mmu.fetch_u8(cpu.pc + 1) as u16)
+            let mmu = &mut cpu.mmu;
+            mmu.fetch_u16(cpu.pc + 1)
         };
         cpu.set_cycles(16);
         cpu.pc = address;
@@ -1023,8 +1041,9 @@ pub fn jp_c_u16(cpu: &mut CPU) {
 
 pub fn pop_bc(cpu: &mut CPU) {
     let (b, c) = {
-        let mmu = cpu.mmu.lock().unwrap();
-        (mmu.fetch_u8(cpu.sp + 1), mmu.fetch_u8(cpu.sp))
+        let mmu = &mut cpu.mmu;
+        let c = mmu.read_byte(cpu.sp);
+        (mmu.read_byte(cpu.sp.wrapping_add(1)), c)
     };
 
     cpu.b = b;
@@ -1036,8 +1055,9 @@ pub fn pop_bc(cpu: &mut CPU) {
 
 pub fn pop_de(cpu: &mut CPU) {
     let (d, e) = {
-        let mmu = cpu.mmu.lock().unwrap();
-        (mmu.fetch_u8(cpu.sp + 1), mmu.fetch_u8(cpu.sp))
+        let mmu = &mut cpu.mmu;
+        let e = mmu.read_byte(cpu.sp);
+        (mmu.read_byte(cpu.sp.wrapping_add(1)), e)
     };
 
     cpu.d = d;
@@ -1049,8 +1069,9 @@ pub fn pop_de(cpu: &mut CPU) {
 
 pub fn pop_hl(cpu: &mut CPU) {
     let (h, l) = {
-        let mmu = cpu.mmu.lock().unwrap();
-        (mmu.fetch_u8(cpu.sp + 1), mmu.fetch_u8(cpu.sp))
+        let mmu = &mut cpu.mmu;
+        let l = mmu.read_byte(cpu.sp);
+        (mmu.read_byte(cpu.sp.wrapping_add(1)), l)
     };
 
     cpu.h = h;
@@ -1062,8 +1083,9 @@ pub fn pop_hl(cpu: &mut CPU) {
 
 pub fn pop_af(cpu: &mut CPU) {
     let (a, f) = {
-        let mmu = cpu.mmu.lock().unwrap();
-        (mmu.fetch_u8(cpu.sp + 1), mmu.fetch_u8(cpu.sp))
+        let mmu = &mut cpu.mmu;
+        let f = mmu.read_byte(cpu.sp);
+        (mmu.read_byte(cpu.sp.wrapping_add(1)), f)
     };
 
     cpu.a = a;
@@ -1082,7 +1104,8 @@ pub fn pop_af(cpu: &mut CPU) {
 pub fn push_bc(cpu: &mut CPU) {
     cpu.sp -= 2;
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
+        mmu.tick(4); // Internal M-cycle before the pushes
         mmu.write_byte(cpu.sp + 1, cpu.b);
         mmu.write_byte(cpu.sp, cpu.c);
     }
@@ -1093,7 +1116,8 @@ pub fn push_bc(cpu: &mut CPU) {
 pub fn push_de(cpu: &mut CPU) {
     cpu.sp -= 2;
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
+        mmu.tick(4); // Internal M-cycle before the pushes
         mmu.write_byte(cpu.sp + 1, cpu.d);
         mmu.write_byte(cpu.sp, cpu.e);
     }
@@ -1104,7 +1128,8 @@ pub fn push_de(cpu: &mut CPU) {
 pub fn push_hl(cpu: &mut CPU) {
     cpu.sp -= 2;
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
+        mmu.tick(4); // Internal M-cycle before the pushes
         mmu.write_byte(cpu.sp + 1, cpu.h);
         mmu.write_byte(cpu.sp, cpu.l);
     }
@@ -1115,7 +1140,8 @@ pub fn push_hl(cpu: &mut CPU) {
 pub fn push_af(cpu: &mut CPU) {
     cpu.sp -= 2;
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
+        mmu.tick(4); // Internal M-cycle before the pushes
         mmu.write_byte(cpu.sp + 1, cpu.a);
         mmu.write_byte(cpu.sp, cpu.f);
     }
@@ -1125,8 +1151,8 @@ pub fn push_af(cpu: &mut CPU) {
 
 pub fn call_u16(cpu: &mut CPU) {
     let target_address = {
-        let mmu = cpu.mmu.lock().unwrap();
-        ((mmu.fetch_u8(cpu.pc + 2) as u16) << 8) | (mmu.fetch_u8(cpu.pc + 1) as u16)
+        let mmu = &mut cpu.mmu;
+        mmu.fetch_u16(cpu.pc + 1)
     };
     cpu.pc += 3;
     let lower_byte = (cpu.pc & 0xFF) as u8;
@@ -1134,7 +1160,8 @@ pub fn call_u16(cpu: &mut CPU) {
     cpu.sp -= 2;
     
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
+        mmu.tick(4); // Internal M-cycle before the pushes
         mmu.write_byte(cpu.sp + 1, upper_byte);
         mmu.write_byte(cpu.sp, lower_byte);
     }
@@ -1146,8 +1173,8 @@ pub fn call_u16(cpu: &mut CPU) {
 pub fn call_nz_u16(cpu: &mut CPU) {
     if (cpu.f & 0x80) >> 7 != 1 {
         let target_address = {
-            let mmu = cpu.mmu.lock().unwrap();
-            ((mmu.fetch_u8(cpu.pc + 2) as u16) << 8) | (mmu.fetch_u8(cpu.pc + 1) as u16)
+            let mmu = &mut cpu.mmu;
+            mmu.fetch_u16(cpu.pc + 1)
         };
         cpu.pc += 3;
         let lower_byte = (cpu.pc & 0xFF) as u8;
@@ -1155,7 +1182,8 @@ pub fn call_nz_u16(cpu: &mut CPU) {
         cpu.sp -= 2;
 
         {
-            let mut mmu = cpu.mmu.lock().unwrap();
+            let mmu = &mut cpu.mmu;
+            mmu.tick(4); // Internal M-cycle before the pushes
             mmu.write_byte(cpu.sp + 1, upper_byte);
             mmu.write_byte(cpu.sp, lower_byte);
         }
@@ -1171,8 +1199,8 @@ pub fn call_nz_u16(cpu: &mut CPU) {
 pub fn call_z_u16(cpu: &mut CPU) {
     if (cpu.f & 0x80) >> 7 == 1 {
         let target_address = {
-            let mmu = cpu.mmu.lock().unwrap();
-            ((mmu.fetch_u8(cpu.pc + 2) as u16) << 8) | (mmu.fetch_u8(cpu.pc + 1) as u16)
+            let mmu = &mut cpu.mmu;
+            mmu.fetch_u16(cpu.pc + 1)
         };
         cpu.pc += 3;
         let lower_byte = (cpu.pc & 0xFF) as u8;
@@ -1180,7 +1208,8 @@ pub fn call_z_u16(cpu: &mut CPU) {
         cpu.sp -= 2;
 
         {
-            let mut mmu = cpu.mmu.lock().unwrap();
+            let mmu = &mut cpu.mmu;
+            mmu.tick(4); // Internal M-cycle before the pushes
             mmu.write_byte(cpu.sp + 1, upper_byte);
             mmu.write_byte(cpu.sp, lower_byte);
         }
@@ -1196,8 +1225,8 @@ pub fn call_z_u16(cpu: &mut CPU) {
 pub fn call_nc_u16(cpu: &mut CPU) {
     if (cpu.f & 0x10) >> 4 != 1 {
         let target_address = {
-            let mmu = cpu.mmu.lock().unwrap();
-            ((mmu.fetch_u8(cpu.pc + 2) as u16) << 8) | (mmu.fetch_u8(cpu.pc + 1) as u16)
+            let mmu = &mut cpu.mmu;
+            mmu.fetch_u16(cpu.pc + 1)
         };
         cpu.pc += 3;
         let lower_byte = (cpu.pc & 0xFF) as u8;
@@ -1205,7 +1234,8 @@ pub fn call_nc_u16(cpu: &mut CPU) {
         cpu.sp -= 2;
 
         {
-            let mut mmu = cpu.mmu.lock().unwrap();
+            let mmu = &mut cpu.mmu;
+            mmu.tick(4); // Internal M-cycle before the pushes
             mmu.write_byte(cpu.sp + 1, upper_byte);
             mmu.write_byte(cpu.sp, lower_byte);
         }
@@ -1221,8 +1251,8 @@ pub fn call_nc_u16(cpu: &mut CPU) {
 pub fn call_c_u16(cpu: &mut CPU) {
     if (cpu.f & 0x10) >> 4 == 1 {
         let target_address = {
-            let mmu = cpu.mmu.lock().unwrap();
-            ((mmu.fetch_u8(cpu.pc + 2) as u16) << 8) | (mmu.fetch_u8(cpu.pc + 1) as u16)
+            let mmu = &mut cpu.mmu;
+            mmu.fetch_u16(cpu.pc + 1)
         };
         cpu.pc += 3;
         let lower_byte = (cpu.pc & 0xFF) as u8;
@@ -1230,7 +1260,8 @@ pub fn call_c_u16(cpu: &mut CPU) {
         cpu.sp -= 2;
 
         {
-            let mut mmu = cpu.mmu.lock().unwrap();
+            let mmu = &mut cpu.mmu;
+            mmu.tick(4); // Internal M-cycle before the pushes
             mmu.write_byte(cpu.sp + 1, upper_byte);
             mmu.write_byte(cpu.sp, lower_byte);
         }
@@ -1246,8 +1277,8 @@ pub fn call_c_u16(cpu: &mut CPU) {
 pub fn ret(cpu: &mut CPU) {
     cpu.set_cycles(16);
     let (new_pc, new_sp) = {
-        let mmu = cpu.mmu.lock().unwrap();
-        let pc = ((mmu.fetch_u8(cpu.sp + 1) as u16) << 8) | (mmu.fetch_u8(cpu.sp) as u16); 
+        let mmu = &mut cpu.mmu;
+        let pc = mmu.read_u16(cpu.sp);
         (pc, cpu.sp + 2)
     };
     cpu.pc = new_pc;
@@ -1257,8 +1288,8 @@ pub fn ret(cpu: &mut CPU) {
 pub fn reti(cpu: &mut CPU) {
     cpu.set_cycles(16);
     let (new_pc, new_sp) = {
-        let mmu = cpu.mmu.lock().unwrap();
-        let pc = ((mmu.fetch_u8(cpu.sp + 1) as u16) << 8) | (mmu.fetch_u8(cpu.sp) as u16); 
+        let mmu = &mut cpu.mmu;
+        let pc = mmu.read_u16(cpu.sp);
         (pc, cpu.sp + 2)
     };
     cpu.pc = new_pc;
@@ -1270,8 +1301,9 @@ pub fn ret_nz(cpu: &mut CPU) {
     if (cpu.f & 0x80) >> 7 != 1 {
         cpu.set_cycles(20);
         let (new_pc, new_sp) = {
-            let mmu = cpu.mmu.lock().unwrap();
-            let pc = ((mmu.fetch_u8(cpu.sp + 1) as u16) << 8) | (mmu.fetch_u8(cpu.sp) as u16); 
+            let mmu = &mut cpu.mmu;
+            mmu.tick(4); // Internal M-cycle checking the condition
+            let pc = mmu.read_u16(cpu.sp);
             (pc, cpu.sp + 2)
         };
         cpu.pc = new_pc;
@@ -1286,8 +1318,9 @@ pub fn ret_z(cpu: &mut CPU) {
     if (cpu.f & 0x80) >> 7 == 1 {
         cpu.set_cycles(20);
         let (new_pc, new_sp) = {
-            let mmu = cpu.mmu.lock().unwrap();
-            let pc = ((mmu.fetch_u8(cpu.sp + 1) as u16) << 8) | (mmu.fetch_u8(cpu.sp) as u16); 
+            let mmu = &mut cpu.mmu;
+            mmu.tick(4); // Internal M-cycle checking the condition
+            let pc = mmu.read_u16(cpu.sp);
             (pc, cpu.sp + 2)
         };
         cpu.pc = new_pc;
@@ -1302,8 +1335,9 @@ pub fn ret_nc(cpu: &mut CPU) {
     if (cpu.f & 0x10) >> 4 != 1 {
         cpu.set_cycles(20);
         let (new_pc, new_sp) = {
-            let mmu = cpu.mmu.lock().unwrap();
-            let pc = ((mmu.fetch_u8(cpu.sp + 1) as u16) << 8) | (mmu.fetch_u8(cpu.sp) as u16); 
+            let mmu = &mut cpu.mmu;
+            mmu.tick(4); // Internal M-cycle checking the condition
+            let pc = mmu.read_u16(cpu.sp);
             (pc, cpu.sp + 2)
         };
         cpu.pc = new_pc;
@@ -1318,8 +1352,9 @@ pub fn ret_c(cpu: &mut CPU) {
     if (cpu.f & 0x10) >> 4 == 1 {
         cpu.set_cycles(20);
         let (new_pc, new_sp) = {
-            let mmu = cpu.mmu.lock().unwrap();
-            let pc = ((mmu.fetch_u8(cpu.sp + 1) as u16) << 8) | (mmu.fetch_u8(cpu.sp) as u16); 
+            let mmu = &mut cpu.mmu;
+            mmu.tick(4); // Internal M-cycle checking the condition
+            let pc = mmu.read_u16(cpu.sp);
             (pc, cpu.sp + 2)
         };
         cpu.pc = new_pc;
@@ -1339,7 +1374,8 @@ pub fn rst(cpu: &mut CPU, address: u16) {
         (lower_byte, upper_byte, cpu.sp.wrapping_sub(2))
     };
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
+        mmu.tick(4); // Internal M-cycle before the pushes
         mmu.write_byte(new_sp.wrapping_add(1), upper_byte);
         mmu.write_byte(new_sp, lower_byte);
     }
@@ -1437,13 +1473,14 @@ pub fn ccf(cpu: &mut CPU) {
 }
 
 pub fn ei(cpu: &mut CPU) {
-    cpu.ime = true;
+    cpu.ime_scheduled = true;
     cpu.set_cycles(4);
     cpu.pc += 1;
 }
 
 pub fn di(cpu: &mut CPU) {
     cpu.ime = false;
+    cpu.ime_scheduled = false;
     cpu.set_cycles(4);
     cpu.pc += 1;        
 }
@@ -1463,7 +1500,7 @@ pub fn rlc_m_hl(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let (value, result) = {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         let value = mmu.read_byte(address);
         let carry_flag = value >> 7;
         let result = (value << 1) | carry_flag;
@@ -1494,7 +1531,7 @@ pub fn rrc_m_hl(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let (value, result) = {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         let value = mmu.read_byte(address);
         let carry_flag = value & 1;
         let result = (value >> 1) | (carry_flag << 7);
@@ -1525,7 +1562,7 @@ pub fn rl_m_hl(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let (value, result) = {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         let value = mmu.read_byte(address);
         let result = (value << 1) | ((cpu.f & 0x10) >> 4);
         mmu.write_byte(address, result);
@@ -1555,7 +1592,7 @@ pub fn rr_m_hl(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let (value, result) = {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         let value = mmu.read_byte(address);
         let result = (value >> 1) | ((cpu.f & 0x10) << 3);
         mmu.write_byte(address, result);
@@ -1585,7 +1622,7 @@ pub fn sla_m_hl(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let (value, result) = {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         let value = mmu.read_byte(address);
         let result = value << 1;
         mmu.write_byte(address, result);
@@ -1615,7 +1652,7 @@ pub fn sra_m_hl(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let (value, result) = {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         let value = mmu.read_byte(address);
         let result = (value >> 1) | (value & 0x80);
         mmu.write_byte(address, result);
@@ -1643,7 +1680,7 @@ pub fn swap_m_hl(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let result = {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         let value = mmu.read_byte(address);
         let result = (value >> 4) | (value << 4);
         mmu.write_byte(address, result);
@@ -1672,7 +1709,7 @@ pub fn srl_m_hl(cpu: &mut CPU) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let result = {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         let value = mmu.read_byte(address);
         let carry_flag = value & 0x01 != 0;
         let result = value >> 1;
@@ -1698,7 +1735,7 @@ pub fn bit_n_r_u8(cpu: &mut CPU, bit: u8, register: u8) {
 pub fn bit_n_m_hl(cpu: &mut CPU, bit: u8) {
     let value = {
         let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.read_byte(address)
     };
 
@@ -1718,13 +1755,13 @@ pub fn res_n_m_hl(cpu: &mut CPU, bit: u8) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let result = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         let value = mmu.read_byte(address);
         value & !(1 << bit)
     };
 
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.write_byte(address, result);
     }
 
@@ -1742,13 +1779,13 @@ pub fn set_n_m_hl(cpu: &mut CPU, bit: u8) {
     let address = ((cpu.h as u16) << 8) | (cpu.l as u16);
 
     let result = {
-        let mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         let value = mmu.read_byte(address);
         value | (1 << bit)
     };
 
     {
-        let mut mmu = cpu.mmu.lock().unwrap();
+        let mmu = &mut cpu.mmu;
         mmu.write_byte(address, result);
     }
 
@@ -1,9 +1,12 @@
 mod instructions;
+pub mod disasm;
+pub mod trace;
 
 use crate::mmu::MMU;
+use crate::savestate::{StateError, StateReader, StateWriter};
 use instructions::*;
+use trace::Tracer;
 
-use std::sync::{Arc, Mutex};
 
 pub enum InterruptAddress {
     VBlank = 0x0040,   // Address for Vertical Blank interrupt
@@ -63,12 +66,17 @@ pub struct CPU {
     pub sp: u16,                   // Stack Pointer. Points to the top of the stack.
     pub ime: bool,                 // Interrupt Master Enable flag. Controls the global interrupt enable/disable state.
     pub halted: bool,              // Halt flag. Indicates if the CPU is in a halted state, waiting for an interrupt.
+    pub ime_scheduled: bool,       // Set by EI. IME is enabled once the instruction following EI has executed.
+    pub halt_bug: bool,            // Set by HALT when it is skipped. The next opcode fetch doesn't increment PC.
+    pub stopped: bool,             // Stop flag. Indicates if the CPU and LCD are stopped until a joypad line goes low.
+    pub locked: bool,              // Lock flag. Set by an illegal opcode, the CPU then hangs until it is reset.
     pub cycles: u16,               // Cycles number. Stores the number of cycles executed by the last instruction.
-    pub mmu: Arc<Mutex<MMU>>,      // Memory Management Unit. Manages access to different memory regions.
+    pub mmu: MMU,                  // Memory Management Unit. Manages access to different memory regions.
+    pub tracer: Option<Tracer>,    // Execution trace logger. Records the CPU state before every instruction when set.
 }
 
 impl CPU {
-    pub fn new(mmu: Arc<Mutex<MMU>>) -> Self {
+    pub fn new(mmu: MMU) -> Self {
         CPU {
             a: 0,
             b: 0,
@@ -82,22 +90,90 @@ impl CPU {
             sp: 0,
             ime: false,
             halted: false,
+            ime_scheduled: false,
+            halt_bug: false,
+            stopped: false,
+            locked: false,
             cycles: 0,
             mmu,
+            tracer: None,
         }
     }
 
+    // Sets the registers the boot ROM leaves behind and continues at the cartridge entry point.
+    pub fn skip_boot(&mut self, registers: [u8; 8]) {
+        [self.a, self.f, self.b, self.c, self.d, self.e, self.h, self.l] = registers;
+        self.sp = 0xFFFE;
+        self.pc = 0x0100;
+    }
+
+    // Jumps to the handler of a pending interrupt when IME allows it. This runs as a step of its own before tick,
+    // so the next instruction tick executes is the first one of the handler. Returns true when an interrupt was taken.
+    pub fn dispatch_interrupts(&mut self) -> bool {
+        if self.stopped || self.locked {
+            return false;
+        }
+
+        let dispatched = self.handle_interrupts();
+        if dispatched {
+            self.mmu.finish_instruction(20);
+        }
+        dispatched
+    }
+
     pub fn tick(&mut self) {
-        let interrupt_handled = self.handle_interrupts();
-        
-        if self.halted && !interrupt_handled {
+        if self.stopped {
             self.set_cycles(4);
+            let mmu = &mut self.mmu;
+            mmu.tick_stopped(4);
+            self.stopped = mmu.peek_byte(0xFF00) & 0x0F == 0x0F;
             return;
         }
-    
-        if !self.halted {
-            let opcode = self.mmu.lock().unwrap().fetch_instruction(self.pc);
+
+        // A locked CPU ignores interrupts, while the rest of the hardware keeps running.
+        if self.locked {
+            self.set_cycles(4);
+            self.mmu.finish_instruction(self.cycles);
+            return;
+        }
+
+        if self.halted {
+            self.set_cycles(4);
+        } else {
+            if self.tracer.is_some() {
+                self.trace();
+            }
+            let ime_scheduled = self.ime_scheduled;
+            let opcode = self.mmu.fetch_instruction(self.pc);
+            if std::mem::take(&mut self.halt_bug) {
+                // Operands are read starting from the opcode itself, and a one byte instruction runs twice.
+                self.pc = self.pc.wrapping_sub(1);
+            }
             self.execute(opcode);
+
+            // A DI right after EI cancels the pending enable.
+            if ime_scheduled && self.ime_scheduled {
+                self.ime = true;
+                self.ime_scheduled = false;
+            }
+        }
+        self.mmu.finish_instruction(self.cycles);
+    }
+
+    pub fn pending_interrupts(&self) -> u8 {
+        let mmu = &self.mmu;
+        mmu.peek_byte(0xFF0F) & mmu.peek_byte(0xFFFF) & 0x1F
+    }
+
+    fn trace(&mut self) {
+        let pcmem = [0, 1, 2, 3].map(|offset| self.mmu.peek_byte(self.pc.wrapping_add(offset)));
+
+        let registers = [self.a, self.f, self.b, self.c, self.d, self.e, self.h, self.l];
+        let line = trace::format_line(registers, self.sp, self.pc, pcmem);
+        if let Some(ref mut tracer) = self.tracer {
+            if tracer.record(line).is_err() {
+                self.tracer = None;
+            }
         }
     }
 
@@ -106,7 +182,7 @@ impl CPU {
             self.execute_not_prefixed_instruction(opcode);
         } else {
             self.pc += 1;
-            let new_opcode = self.mmu.lock().unwrap().fetch_instruction(self.pc);
+            let new_opcode = self.mmu.fetch_instruction(self.pc);
             self.execute_prefixed_instruction(new_opcode);
         }
     }
@@ -119,49 +195,86 @@ impl CPU {
         self.cycles
     }
 
-    pub fn request_interrupt(&mut self, interrupt: u8) {
-        let mut mmu = self.mmu.lock().unwrap();
-        let current_interrupt_flag = mmu.read_byte(0xFF0F);
-        let new_interrupt_flag = current_interrupt_flag | interrupt;
-        mmu.write_byte(0xFF0F, new_interrupt_flag);
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        for register in [self.a, self.b, self.c, self.d, self.e, self.f, self.h, self.l] {
+            writer.write_u8(register);
+        }
+        writer.write_u16(self.pc);
+        writer.write_u16(self.sp);
+        writer.write_bool(self.ime);
+        writer.write_bool(self.halted);
+        writer.write_bool(self.ime_scheduled);
+        writer.write_bool(self.halt_bug);
+        writer.write_bool(self.stopped);
+        writer.write_bool(self.locked);
+        writer.write_u16(self.cycles);
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.a = reader.read_u8()?;
+        self.b = reader.read_u8()?;
+        self.c = reader.read_u8()?;
+        self.d = reader.read_u8()?;
+        self.e = reader.read_u8()?;
+        self.f = reader.read_u8()?;
+        self.h = reader.read_u8()?;
+        self.l = reader.read_u8()?;
+        self.pc = reader.read_u16()?;
+        self.sp = reader.read_u16()?;
+        self.ime = reader.read_bool()?;
+        self.halted = reader.read_bool()?;
+        self.ime_scheduled = reader.read_bool()?;
+        self.halt_bug = reader.read_bool()?;
+        self.stopped = reader.read_bool()?;
+        self.locked = reader.read_bool()?;
+        self.cycles = reader.read_u16()?;
+        Ok(())
     }
 
     fn handle_interrupts(&mut self) -> bool {
-        let mut mmu = self.mmu.lock().unwrap();
-        let interrupt_flag = mmu.read_byte(0xFF0F);
-        let interrupt_enable = mmu.read_byte(0xFFFF);
-        let pending_interrupts = interrupt_flag & interrupt_enable;
+        let mmu = &mut self.mmu;
+        let interrupt_flag = mmu.peek_byte(0xFF0F);
+        let interrupt_enable = mmu.peek_byte(0xFFFF);
+        let pending_interrupts = interrupt_flag & interrupt_enable & 0x1F;
     
         if pending_interrupts == 0 {
             return false;
         }
     
-        if self.halted {
-            self.halted = false;
-            self.pc += 1;
-        }
+        // A pending interrupt ends HALT even when IME is off, execution then continues after HALT.
+        self.halted = false;
     
         if self.ime {
+            // An interrupt taken right after a skipped HALT returns to the HALT itself.
+            if std::mem::take(&mut self.halt_bug) {
+                self.pc = self.pc.wrapping_sub(1);
+            }
 
             self.ime = false;
+            self.ime_scheduled = false;
+            // Two internal M-cycles, the pushes of PC and one more M-cycle to jump to the handler.
+            mmu.tick(8);
             self.sp -= 2;
             mmu.write_byte(self.sp + 1, (self.pc >> 8) as u8);
             mmu.write_byte(self.sp, (self.pc & 0xFF) as u8);
+            mmu.tick(4);
+            // Interrupts raised while the pushes ran must stay requested.
+            let interrupt_flag = mmu.peek_byte(0xFF0F);
     
             if pending_interrupts & InterruptFlag::VBlank.value() != 0 {
-                mmu.write_byte(0xFF0F, interrupt_flag & !InterruptFlag::VBlank.value());
+                mmu.poke_byte(0xFF0F, interrupt_flag & !InterruptFlag::VBlank.value());
                 self.pc = InterruptAddress::VBlank.address();
             } else if pending_interrupts & InterruptFlag::LCDStat.value() != 0 {
-                mmu.write_byte(0xFF0F, interrupt_flag & !InterruptFlag::LCDStat.value());
+                mmu.poke_byte(0xFF0F, interrupt_flag & !InterruptFlag::LCDStat.value());
                 self.pc = InterruptAddress::LCDStat.address();
             } else if pending_interrupts & InterruptFlag::Timer.value() != 0 {
-                mmu.write_byte(0xFF0F, interrupt_flag & !InterruptFlag::Timer.value());
+                mmu.poke_byte(0xFF0F, interrupt_flag & !InterruptFlag::Timer.value());
                 self.pc = InterruptAddress::Timer.address();
             } else if pending_interrupts & InterruptFlag::Serial.value() != 0 {
-                mmu.write_byte(0xFF0F, interrupt_flag & !InterruptFlag::Serial.value());
+                mmu.poke_byte(0xFF0F, interrupt_flag & !InterruptFlag::Serial.value());
                 self.pc = InterruptAddress::Serial.address();
             } else if pending_interrupts & InterruptFlag::Joypad.value() != 0 {
-                mmu.write_byte(0xFF0F, interrupt_flag & !InterruptFlag::Joypad.value());
+                mmu.poke_byte(0xFF0F, interrupt_flag & !InterruptFlag::Joypad.value());
                 self.pc = InterruptAddress::Joypad.address();
             }
             return true;
@@ -1103,7 +1216,9 @@ impl CPU {
                     7 => {// EI
                         ei(self);
                     },
-                    _ => unreachable!(),
+                    _ => {// Illegal opcode
+                        lock(self);
+                    },
                 }
             },
             4 => {
@@ -1120,7 +1235,9 @@ impl CPU {
                     3 => {// CALL C, nn
                         call_c_u16(self);
                     },
-                    _ => unreachable!(),
+                    _ => {// Illegal opcode
+                        lock(self);
+                    },
                 }
             },
             5 => {
@@ -1140,7 +1257,9 @@ impl CPU {
                     6 => {// PUSH AF
                         push_af(self);
                     },
-                    _ => unreachable!(),
+                    _ => {// Illegal opcode
+                        lock(self);
+                    },
                 }
             },
             6 => {
@@ -0,0 +1,63 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+const MAX_BUFFER_LINES: usize = 1_000_000; // Roughly two seconds of emulation, about 100 MB of text
+
+enum TraceSink {
+    File(BufWriter<File>),
+    Buffer { lines: VecDeque<String>, capacity: usize }, // Keeps only the newest `capacity` lines
+}
+
+// Records one line per executed instruction in the Gameboy Doctor format, so that logs can be diffed
+// against reference traces.
+pub struct Tracer {
+    sink: TraceSink,
+}
+
+impl Tracer {
+    pub fn to_file(path: &str) -> io::Result<Self> {
+        Ok(Tracer { sink: TraceSink::File(BufWriter::new(File::create(path)?)) })
+    }
+
+    // The capacity comes from the host, so it is clamped to MAX_BUFFER_LINES and the buffer grows as lines arrive.
+    pub fn to_buffer(capacity: usize) -> Self {
+        Tracer {
+            sink: TraceSink::Buffer { lines: VecDeque::new(), capacity: capacity.clamp(1, MAX_BUFFER_LINES) },
+        }
+    }
+
+    pub fn record(&mut self, line: String) -> io::Result<()> {
+        match self.sink {
+            TraceSink::File(ref mut file) => writeln!(file, "{}", line),
+            TraceSink::Buffer { ref mut lines, capacity } => {
+                if lines.len() == capacity {
+                    lines.pop_front();
+                }
+                lines.push_back(line);
+                Ok(())
+            },
+        }
+    }
+
+    // Buffered lines, oldest first. File traces are flushed instead and return nothing.
+    pub fn lines(&mut self) -> Vec<String> {
+        match self.sink {
+            TraceSink::File(ref mut file) => {
+                let _ = file.flush();
+                Vec::new()
+            },
+            TraceSink::Buffer { ref lines, .. } => lines.iter().cloned().collect(),
+        }
+    }
+}
+
+// Formats the registers and the four bytes at PC, e.g.
+// "A:01 F:B0 B:00 C:13 D:00 E:D8 H:01 L:4D SP:FFFE PC:0100 PCMEM:00,C3,13,02".
+pub fn format_line(registers: [u8; 8], sp: u16, pc: u16, pcmem: [u8; 4]) -> String {
+    let [a, f, b, c, d, e, h, l] = registers;
+    format!(
+        "A:{:02X} F:{:02X} B:{:02X} C:{:02X} D:{:02X} E:{:02X} H:{:02X} L:{:02X} SP:{:04X} PC:{:04X} PCMEM:{:02X},{:02X},{:02X},{:02X}",
+        a, f, b, c, d, e, h, l, sp, pc, pcmem[0], pcmem[1], pcmem[2], pcmem[3]
+    )
+}
@@ -0,0 +1,285 @@
+use crate::cpu::CPU;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum StopKind {
+    Breakpoint,
+    ReadWatchpoint,
+    WriteWatchpoint,
+    ExecuteWatchpoint,
+    Interrupt,
+}
+
+#[derive(Clone)]
+pub struct StopReason {
+    pub kind: StopKind,
+    pub address: u16, // Breakpoint address, accessed address for watchpoints or the interrupt vector
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Access {
+    Read,
+    Write,
+    Execute,
+}
+
+#[derive(Clone, Copy)]
+pub struct Watchpoint {
+    pub start: u16,
+    pub end: u16, // Inclusive
+    pub read: bool,
+    pub write: bool,
+    pub execute: bool,
+}
+
+impl Watchpoint {
+    pub fn check(&self, address: u16, access: Access) -> Option<StopReason> {
+        if address < self.start || address > self.end {
+            return None;
+        }
+
+        let kind = match access {
+            Access::Read if self.read => StopKind::ReadWatchpoint,
+            Access::Write if self.write => StopKind::WriteWatchpoint,
+            Access::Execute if self.execute => StopKind::ExecuteWatchpoint,
+            _ => return None,
+        };
+        Some(StopReason { kind, address })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Register {
+    A, B, C, D, E, F, H, L,
+    AF, BC, DE, HL, SP, PC,
+}
+
+impl Register {
+    fn parse(name: &str) -> Option<Register> {
+        match name {
+            "a" => Some(Register::A),
+            "b" => Some(Register::B),
+            "c" => Some(Register::C),
+            "d" => Some(Register::D),
+            "e" => Some(Register::E),
+            "f" => Some(Register::F),
+            "h" => Some(Register::H),
+            "l" => Some(Register::L),
+            "af" => Some(Register::AF),
+            "bc" => Some(Register::BC),
+            "de" => Some(Register::DE),
+            "hl" => Some(Register::HL),
+            "sp" => Some(Register::SP),
+            "pc" => Some(Register::PC),
+            _ => None,
+        }
+    }
+
+    fn value(self, cpu: &CPU) -> u16 {
+        let pair = |high: u8, low: u8| (high as u16) << 8 | low as u16;
+        match self {
+            Register::A => cpu.a as u16,
+            Register::B => cpu.b as u16,
+            Register::C => cpu.c as u16,
+            Register::D => cpu.d as u16,
+            Register::E => cpu.e as u16,
+            Register::F => cpu.f as u16,
+            Register::H => cpu.h as u16,
+            Register::L => cpu.l as u16,
+            Register::AF => pair(cpu.a, cpu.f),
+            Register::BC => pair(cpu.b, cpu.c),
+            Register::DE => pair(cpu.d, cpu.e),
+            Register::HL => pair(cpu.h, cpu.l),
+            Register::SP => cpu.sp,
+            Register::PC => cpu.pc,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+// A register comparison such as "a == 0x10" or "hl >= $C000".
+pub struct Condition {
+    register: Register,
+    comparison: Comparison,
+    value: u16,
+}
+
+impl Condition {
+    pub fn parse(text: &str) -> Option<Condition> {
+        let text = text.trim().to_lowercase();
+        let operators = [
+            ("==", Comparison::Equal),
+            ("!=", Comparison::NotEqual),
+            ("<=", Comparison::LessOrEqual),
+            (">=", Comparison::GreaterOrEqual),
+            ("<", Comparison::Less),
+            (">", Comparison::Greater),
+        ];
+        let (operator, comparison) = operators.into_iter().find(|(operator, _)| text.contains(operator))?;
+        let (register, value) = text.split_once(operator)?;
+
+        Some(Condition {
+            register: Register::parse(register.trim())?,
+            comparison,
+            value: parse_number(value.trim())?,
+        })
+    }
+
+    fn holds(&self, cpu: &CPU) -> bool {
+        let register = self.register.value(cpu);
+        match self.comparison {
+            Comparison::Equal => register == self.value,
+            Comparison::NotEqual => register != self.value,
+            Comparison::Less => register < self.value,
+            Comparison::LessOrEqual => register <= self.value,
+            Comparison::Greater => register > self.value,
+            Comparison::GreaterOrEqual => register >= self.value,
+        }
+    }
+}
+
+fn parse_number(text: &str) -> Option<u16> {
+    if let Some(hex) = text.strip_prefix("0x").or_else(|| text.strip_prefix('$')) {
+        u16::from_str_radix(hex, 16).ok()
+    } else {
+        text.parse().ok()
+    }
+}
+
+struct Breakpoint {
+    address: u16,
+    bank: Option<u16>, // ROM bank the address has to be mapped from, any bank when unset
+    condition: Option<Condition>,
+    remote: bool,      // Added by the GDB client, which must not touch the breakpoints set by the app
+}
+
+#[derive(Default)]
+pub struct DEBUGGER {
+    breakpoints: Vec<Breakpoint>,
+    watchpoints: Vec<Watchpoint>,
+    break_on_interrupt: bool,
+    stop_reason: Option<StopReason>,
+    resuming: bool, // Lets the instruction the emulator stopped on execute once execution continues
+}
+
+impl DEBUGGER {
+    pub fn new() -> Self {
+        DEBUGGER {
+            breakpoints: Vec::new(),
+            watchpoints: Vec::new(),
+            break_on_interrupt: false,
+            stop_reason: None,
+            resuming: false,
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, address: u16, bank: Option<u16>, condition: Option<Condition>) {
+        self.remove_breakpoint(address, bank);
+        self.breakpoints.push(Breakpoint { address, bank, condition, remote: false });
+    }
+
+    pub fn remove_breakpoint(&mut self, address: u16, bank: Option<u16>) {
+        self.breakpoints.retain(|breakpoint| {
+            breakpoint.remote || breakpoint.address != address || breakpoint.bank != bank
+        });
+    }
+
+    pub fn add_remote_breakpoint(&mut self, address: u16) {
+        self.remove_remote_breakpoint(address);
+        self.breakpoints.push(Breakpoint { address, bank: None, condition: None, remote: true });
+    }
+
+    pub fn remove_remote_breakpoint(&mut self, address: u16) {
+        self.breakpoints.retain(|breakpoint| !breakpoint.remote || breakpoint.address != address);
+    }
+
+    pub fn add_watchpoint(&mut self, watchpoint: Watchpoint) {
+        self.watchpoints.push(watchpoint);
+    }
+
+    pub fn remove_watchpoint(&mut self, start: u16, end: u16) {
+        self.watchpoints.retain(|watchpoint| watchpoint.start != start || watchpoint.end != end);
+    }
+
+    pub fn watchpoints(&self) -> &[Watchpoint] {
+        &self.watchpoints
+    }
+
+    // Breakpoints of a connected GDB client stay in place.
+    pub fn clear(&mut self) {
+        self.breakpoints.retain(|breakpoint| breakpoint.remote);
+        self.watchpoints.clear();
+        self.break_on_interrupt = false;
+    }
+
+    pub fn set_break_on_interrupt(&mut self, enabled: bool) {
+        self.break_on_interrupt = enabled;
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.breakpoints.is_empty() || !self.watchpoints.is_empty() || self.break_on_interrupt
+    }
+
+    pub fn resume(&mut self) {
+        self.stop_reason = None;
+        self.resuming = true;
+    }
+
+    pub fn stop(&mut self, reason: StopReason) {
+        if self.stop_reason.is_none() {
+            self.stop_reason = Some(reason);
+        }
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stop_reason.is_some()
+    }
+
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        self.stop_reason.clone()
+    }
+
+    // Runs before the CPU executes its next instruction, returns true when execution has to stop. The vector is
+    // set when the CPU has just jumped to an interrupt handler.
+    pub fn check_instruction(&mut self, cpu: &CPU, rom_bank: u16, interrupt_vector: Option<u16>) -> bool {
+        if std::mem::take(&mut self.resuming) {
+            return false;
+        }
+
+        if self.break_on_interrupt {
+            if let Some(vector) = interrupt_vector {
+                self.stop(StopReason { kind: StopKind::Interrupt, address: vector });
+                return true;
+            }
+        }
+
+        if cpu.halted || cpu.stopped || cpu.locked {
+            return false;
+        }
+
+        let hit_breakpoint = self.breakpoints.iter().any(|breakpoint| {
+            breakpoint.address == cpu.pc
+                && breakpoint.bank.is_none_or(|bank| bank == rom_bank)
+                && breakpoint.condition.as_ref().is_none_or(|condition| condition.holds(cpu))
+        });
+        if hit_breakpoint {
+            self.stop(StopReason { kind: StopKind::Breakpoint, address: cpu.pc });
+            return true;
+        }
+
+        let execute_hit = self.watchpoints.iter().find_map(|watchpoint| watchpoint.check(cpu.pc, Access::Execute));
+        if let Some(reason) = execute_hit {
+            self.stop(reason);
+            return true;
+        }
+        false
+    }
+}
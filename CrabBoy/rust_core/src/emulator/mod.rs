@@ -1,57 +1,161 @@
-use std::sync::{Arc, Mutex};
-
 use crate::cpu::CPU;
-use crate::ppu::PPU;
-use crate::apu::APU;
+use crate::cpu::disasm::{self, Instruction};
+use crate::cpu::trace::Tracer;
+use crate::apu::{self, ChannelState, HighPassModel};
 use crate::mmu::MMU;
-use crate::timer::TIMER;
-use crate::joypad::JOYPAD;
 use crate::cartridge::CARTRIDGE;
+use crate::bootrom::{BOOTROM, Model, PostBootState};
+use crate::error::CoreError;
+use crate::savestate::{self, StateError, StateReader, StateWriter};
+use crate::rewind::{self, RewindBuffer};
+use crate::debugger::{Condition, DEBUGGER, StopReason, Watchpoint};
+
+const MIN_SPEED: f64 = 0.125;
+const MAX_SPEED: f64 = 8.0;
+const CYCLES_PER_FRAME: u32 = 70224;
+const CYCLES_PER_SCANLINE: u32 = 456;
+
+#[derive(Clone)]
+pub struct FrameOutput {
+    pub frame: Vec<u32>,                 // Last completed frame
+    pub stop_reason: Option<StopReason>, // Set while the debugger holds execution
+}
+
+#[derive(Clone)]
+pub struct AudioSyncOutput {
+    pub frame: Vec<u32>,                 // Last completed frame
+    pub samples: Vec<i16>,               // Interleaved stereo PCM produced while running
+    pub stop_reason: Option<StopReason>, // Set while the debugger holds execution
+}
+
+#[derive(Clone)]
+pub struct MachineState {
+    pub a: u8,
+    pub b: u8,
+    pub c: u8,
+    pub d: u8,
+    pub e: u8,
+    pub f: u8,
+    pub h: u8,
+    pub l: u8,
+    pub pc: u16,
+    pub sp: u16,
+    pub ime: bool,
+    pub halted: bool,
+    pub ly: u8,       // Scanline the PPU is currently on
+    pub ppu_mode: u8, // 0 H-Blank, 1 V-Blank, 2 OAM Search, 3 VRAM access
+}
 
 pub struct EMULATOR {
-    joypad: Arc<Mutex<JOYPAD>>,
-    timer: Arc<Mutex<TIMER>>,
-    ppu: Arc<Mutex<PPU>>,
-    apu: Arc<Mutex<APU>>,
-    mmu: Arc<Mutex<MMU>>,
-    cpu: Arc<Mutex<CPU>>,
+    cpu: CPU, // Owns the bus, which owns every other component
+    rewind: RewindBuffer,
+    debugger: DEBUGGER,
+    speed: f64,             // Emulated frames per rendered frame
+    frame_progress: f64,    // Fraction of a frame carried over between calls at fractional speeds
+    remote_debugging: bool, // A remote debugger drives execution, so rendering only returns the current frame
+    stopped_cycles: u32,    // Cycles spent in STOP mode since the last frame was reported
 }
 
 impl EMULATOR {
-    pub fn new(cartridge: CARTRIDGE) -> Self {
-        let timer = Arc::new(Mutex::new(TIMER::new()));
-        let ppu = Arc::new(Mutex::new(PPU::new()));
-        let apu = Arc::new(Mutex::new(APU::new()));
-        let joypad = Arc::new(Mutex::new(JOYPAD::new()));
-        let mmu = Arc::new(Mutex::new(MMU::new(
-            Arc::clone(&joypad),
-            Arc::clone(&timer),
-            Arc::clone(&apu),
-            Arc::clone(&ppu),
-            cartridge,
-        )));
-        ppu.lock().unwrap().set_mmu(Arc::clone(&mmu));
-        let cpu = Arc::new(Mutex::new(CPU::new(Arc::clone(&mmu))));
-        timer.lock().unwrap().set_cpu(Arc::clone(&cpu));
-        joypad.lock().unwrap().set_cpu(Arc::clone(&cpu));
-        ppu.lock().unwrap().set_cpu(Arc::clone(&cpu));
-
-        EMULATOR {
-            joypad,
-            timer,
-            ppu,
-            apu,
-            mmu,
-            cpu,
+    pub fn new(cartridge: CARTRIDGE, model: Model, boot_rom: Option<Vec<u8>>) -> Result<Self, CoreError> {
+        let post_boot_state = PostBootState::new(model, &cartridge);
+        let mmu = MMU::new(BOOTROM::new(boot_rom, model)?, cartridge, model)?;
+        let mut cpu = CPU::new(mmu);
+
+        if !cpu.mmu.has_boot_rom() {
+            cpu.mmu.skip_boot(&post_boot_state);
+            cpu.skip_boot(post_boot_state.registers);
         }
+        if model == Model::Cgb {
+            cpu.mmu.apu.set_high_pass_model(HighPassModel::Cgb);
+        }
+
+        Ok(EMULATOR {
+            cpu,
+            rewind: RewindBuffer::new(rewind::DEFAULT_MEMORY_BUDGET, rewind::DEFAULT_FRAMES_PER_SNAPSHOT),
+            debugger: DEBUGGER::new(),
+            speed: 1.0,
+            frame_progress: 0.0,
+            remote_debugging: false,
+            stopped_cycles: 0,
+        })
     }
 
     pub fn save_ram(&self) -> Option<Vec<u8>> {
-        self.mmu.lock().unwrap().save_ram()
+        self.cpu.mmu.save_ram()
     } 
 
+    // Title and checksums from the cartridge header, used to tie a save state to its game.
+    fn cartridge_fingerprint(&self) -> [u8; 28] {
+        let mmu = &self.cpu.mmu;
+        let mut fingerprint = [0; 28];
+        for (offset, byte) in fingerprint.iter_mut().enumerate() {
+            *byte = mmu.peek_byte(0x0134 + offset as u16);
+        }
+        fingerprint
+    }
+
+    pub fn save_state(&self) -> Vec<u8> {
+        let mut writer = StateWriter::new();
+        writer.write_bytes(&savestate::STATE_MAGIC);
+        writer.write_u16(savestate::STATE_VERSION);
+        writer.write_bytes(&self.cartridge_fingerprint());
+        self.save_components(&mut writer);
+        writer.into_bytes()
+    }
+
+    fn save_components(&self, writer: &mut StateWriter) {
+        self.cpu.save_state(writer);
+        self.cpu.mmu.save_state(writer);
+        self.cpu.mmu.ppu.save_state(writer);
+        self.cpu.mmu.timer.save_state(writer);
+        self.cpu.mmu.joypad.save_state(writer);
+        self.cpu.mmu.apu.save_state(writer);
+    }
+
+    pub fn load_state(&mut self, state: &[u8]) -> Result<(), CoreError> {
+        let mut reader = StateReader::new(state);
+        let mut magic = [0; 4];
+        reader.read_bytes(&mut magic).map_err(|_| StateError::InvalidHeader)?;
+        if magic != savestate::STATE_MAGIC {
+            return Err(StateError::InvalidHeader.into());
+        }
+        let version = reader.read_u16()?;
+        if version != savestate::STATE_VERSION {
+            return Err(StateError::UnsupportedVersion(version).into());
+        }
+        let mut fingerprint = [0; 28];
+        reader.read_bytes(&mut fingerprint)?;
+        if fingerprint != self.cartridge_fingerprint() {
+            return Err(StateError::CartridgeMismatch.into());
+        }
+
+        // A state that turns out to be broken halfway through must not leave the emulator half restored.
+        let mut backup = StateWriter::new();
+        self.save_components(&mut backup);
+        if let Err(error) = self.restore_components(&mut reader) {
+            let backup = backup.into_bytes();
+            self.restore_components(&mut StateReader::new(&backup)).map_err(|_| CoreError::CorruptState)?;
+            return Err(error.into());
+        }
+        Ok(())
+    }
+
+    fn restore_components(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.cpu.load_state(reader)?;
+        self.cpu.mmu.load_state(reader)?;
+        self.cpu.mmu.ppu.load_state(reader)?;
+        self.cpu.mmu.timer.load_state(reader)?;
+        self.cpu.mmu.joypad.load_state(reader)?;
+        self.cpu.mmu.apu.load_state(reader)?;
+        if !reader.is_finished() {
+            return Err(StateError::InvalidData);
+        }
+        Ok(())
+    }
+
     pub fn set_button_states(&mut self, up: u8, down: u8, left: u8, right: u8, a: u8, b: u8, start: u8, select: u8) {
-        let mut joypad = self.joypad.lock().unwrap();
+        let joypad = &mut self.cpu.mmu.joypad;
         joypad.set_button_state(0b00000100, up == 0);      // Up
         joypad.set_button_state(0b00001000, down == 0);    // Down
         joypad.set_button_state(0b00000010, left == 0);    // Left
@@ -60,39 +164,355 @@ impl EMULATOR {
         joypad.set_button_state(0b00100000, b == 0);       // B
         joypad.set_button_state(0b10000000, start == 0);   // Start
         joypad.set_button_state(0b01000000, select == 0);  // Select
+        if joypad.check_for_interrupt() {
+            self.cpu.mmu.request_interrupt(0b00010000);
+        }
+    }
+
+    pub fn set_audio_sample_rate(&mut self, sample_rate: u32) {
+        self.cpu.mmu.apu.set_sample_rate(sample_rate);
+    }
+
+    pub fn set_audio_high_pass_model(&mut self, model: HighPassModel) {
+        self.cpu.mmu.apu.set_high_pass_model(model);
+    }
+
+    pub fn set_audio_channel_muted(&mut self, channel: usize, muted: bool) {
+        self.cpu.mmu.apu.set_channel_muted(channel, muted);
     }
 
-    pub fn render_frame(&mut self) -> Vec<u32> {
+    pub fn set_audio_channel_soloed(&mut self, channel: usize, soloed: bool) {
+        self.cpu.mmu.apu.set_channel_soloed(channel, soloed);
+    }
+
+    pub fn audio_channel_states(&self) -> Vec<ChannelState> {
+        self.cpu.mmu.apu.channel_states()
+    }
+
+    pub fn take_audio_samples(&mut self) -> Vec<i16> {
+        self.cpu.mmu.sync_apu();
+        self.cpu.mmu.apu
+            .take_samples()
+            .into_iter()
+            .map(apu::to_pcm16)
+            .collect()
+    }
+
+    pub fn start_audio_recording(&mut self) {
+        self.cpu.mmu.apu.start_recording();
+    }
+
+    pub fn stop_audio_recording(&mut self) -> Option<Vec<u8>> {
+        self.cpu.mmu.sync_apu();
+        self.cpu.mmu.apu.stop_recording()
+    }
+
+    fn step(&mut self) -> bool {
+        // Interrupts are taken before the debugger looks at the CPU, so it sees the instruction that really runs next.
+        let interrupted = self.cpu.dispatch_interrupts();
+        if let Some(reason) = self.cpu.mmu.take_watch_hit() {
+            self.debugger.stop(reason);
+        }
+        if (self.debugger.is_active() && self.check_debugger(interrupted)) || self.debugger.is_stopped() {
+            return false;
+        }
+
+        // The CPU advances the bus one M-cycle per memory access, which fires the component events falling into it.
+        self.cpu.tick();
+        self.stopped_cycles += self.stopped_step_cycles();
+
+        if let Some(reason) = self.cpu.mmu.take_watch_hit() {
+            self.debugger.stop(reason);
+        }
+
+        let ppu = &mut self.cpu.mmu.ppu;
+        let mut frame_ready = ppu.is_frame_ready();
+        if frame_ready {
+            ppu.reset_frame_ready();
+        }
+
+        // The LCD is stopped together with the CPU, so frames are counted by time to keep the frontend going.
+        if self.stopped_cycles >= CYCLES_PER_FRAME {
+            self.stopped_cycles -= CYCLES_PER_FRAME;
+            frame_ready = true;
+        }
+
+        if frame_ready && self.rewind.frame_completed() {
+            let state = self.save_state();
+            self.rewind.push(state);
+        }
+        frame_ready
+    }
+
+    // Cycles of the last step spent in STOP mode, counted at normal speed.
+    fn stopped_step_cycles(&self) -> u32 {
+        if !self.cpu.stopped {
+            return 0;
+        }
+        let cycles = self.cpu.get_cycles() as u32;
+        if self.cpu.mmu.is_double_speed() { cycles / 2 } else { cycles }
+    }
+
+    // Returns true when a breakpoint, execute watchpoint or a just taken interrupt stops the next instruction.
+    fn check_debugger(&mut self, interrupted: bool) -> bool {
+        let cpu = &self.cpu;
+        let rom_bank = cpu.mmu.rom_bank(cpu.pc);
+        let vector = if interrupted { Some(cpu.pc) } else { None };
+
+        self.debugger.check_instruction(cpu, rom_bank, vector)
+    }
+
+    // Returns false when the debugger stopped execution before the frame was completed.
+    pub fn run_until_frame(&mut self) -> bool {
         loop {
-            let mut cpu = self.cpu.lock().unwrap();
-            cpu.tick();
-            let cycles = cpu.get_cycles() as u16;
-            drop(cpu);
-    
-            let mut ppu = self.ppu.lock().unwrap();
-            ppu.dma_transfer();
-            ppu.tick(cycles);
-            drop(ppu);
-    
-            let mut timer = self.timer.lock().unwrap();
-            timer.tick(cycles);
-            drop(timer);
-    
-            let mut joypad = self.joypad.lock().unwrap();
-            joypad.check_for_interrupt();
-            drop(joypad);
-
-            let mut ppu = self.ppu.lock().unwrap();
-            let frame_ready = ppu.is_frame_ready();
-            if frame_ready {
-                ppu.reset_frame_ready();
-                drop(ppu);
+            if self.step() {
+                return true;
+            }
+            if self.debugger.is_stopped() {
+                return false;
+            }
+        }
+    }
+
+    pub fn add_breakpoint(&mut self, address: u16, bank: Option<u16>, condition: Option<&str>) -> bool {
+        let condition = match condition.map(Condition::parse) {
+            Some(None) => return false,
+            Some(condition) => condition,
+            None => None,
+        };
+        self.debugger.add_breakpoint(address, bank, condition);
+        true
+    }
+
+    pub fn remove_breakpoint(&mut self, address: u16, bank: Option<u16>) {
+        self.debugger.remove_breakpoint(address, bank);
+    }
+
+    // Breakpoints of the GDB stub are kept apart from the ones the app sets at the same address.
+    pub fn add_remote_breakpoint(&mut self, address: u16) {
+        self.debugger.add_remote_breakpoint(address);
+    }
+
+    pub fn remove_remote_breakpoint(&mut self, address: u16) {
+        self.debugger.remove_remote_breakpoint(address);
+    }
+
+    pub fn add_watchpoint(&mut self, watchpoint: Watchpoint) {
+        self.debugger.add_watchpoint(watchpoint);
+        self.sync_watchpoints();
+    }
+
+    pub fn remove_watchpoint(&mut self, start: u16, end: u16) {
+        self.debugger.remove_watchpoint(start, end);
+        self.sync_watchpoints();
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.debugger.clear();
+        self.sync_watchpoints();
+    }
+
+    fn sync_watchpoints(&mut self) {
+        let watchpoints = self.debugger.watchpoints().to_vec();
+        self.cpu.mmu.set_watchpoints(watchpoints);
+    }
+
+    pub fn set_break_on_interrupt(&mut self, enabled: bool) {
+        self.debugger.set_break_on_interrupt(enabled);
+    }
+
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        self.debugger.stop_reason()
+    }
+
+    // Continues past the instruction execution stopped on.
+    pub fn resume(&mut self) {
+        self.debugger.resume();
+    }
+
+    pub fn set_remote_debugging(&mut self, enabled: bool) {
+        self.remote_debugging = enabled;
+    }
+
+    pub fn configure_rewind(&mut self, memory_budget: usize, frames_per_snapshot: u32) {
+        self.rewind = RewindBuffer::new(memory_budget, frames_per_snapshot);
+    }
+
+    pub fn rewind(&mut self, frames: u32) -> u32 {
+        let (state, rewound) = match self.rewind.rewind(frames) {
+            Some(snapshot) => snapshot,
+            None => return 0,
+        };
+        if self.load_state(&state).is_err() {
+            self.rewind.clear();
+            return 0;
+        }
+        rewound
+    }
+
+    pub fn machine_state(&self) -> MachineState {
+        let cpu = &self.cpu;
+        let ppu = &cpu.mmu.ppu;
+        MachineState {
+            a: cpu.a,
+            b: cpu.b,
+            c: cpu.c,
+            d: cpu.d,
+            e: cpu.e,
+            f: cpu.f,
+            h: cpu.h,
+            l: cpu.l,
+            pc: cpu.pc,
+            sp: cpu.sp,
+            ime: cpu.ime,
+            halted: cpu.halted,
+            ly: ppu.ly(),
+            ppu_mode: ppu.mode(),
+        }
+    }
+
+    pub fn set_registers(&mut self, state: &MachineState) {
+        let cpu = &mut self.cpu;
+        cpu.a = state.a;
+        cpu.b = state.b;
+        cpu.c = state.c;
+        cpu.d = state.d;
+        cpu.e = state.e;
+        cpu.f = state.f & 0xF0;
+        cpu.h = state.h;
+        cpu.l = state.l;
+        cpu.pc = state.pc;
+        cpu.sp = state.sp;
+    }
+
+    pub fn read_memory(&self, address: u16, length: usize) -> Vec<u8> {
+        let mmu = &self.cpu.mmu;
+        (0..length).map(|offset| mmu.peek_byte(address.wrapping_add(offset as u16))).collect()
+    }
+
+    pub fn write_memory(&mut self, address: u16, data: &[u8]) {
+        let mmu = &mut self.cpu.mmu;
+        for (offset, value) in data.iter().enumerate() {
+            mmu.poke_byte(address.wrapping_add(offset as u16), *value);
+        }
+    }
+
+    pub fn step_instruction(&mut self) -> MachineState {
+        self.debugger.resume();
+        self.step();
+        self.machine_state()
+    }
+
+    pub fn step_scanline(&mut self) -> MachineState {
+        self.debugger.resume();
+        let ly = self.cpu.mmu.ppu.ly();
+        // LY is frozen in STOP mode, so there a scanline is measured by time.
+        let mut stopped_cycles = 0;
+        while self.cpu.mmu.ppu.ly() == ly && stopped_cycles < CYCLES_PER_SCANLINE && !self.debugger.is_stopped() {
+            self.step();
+            stopped_cycles += self.stopped_step_cycles();
+        }
+        self.machine_state()
+    }
+
+    pub fn step_frame(&mut self) -> MachineState {
+        self.debugger.resume();
+        self.run_until_frame();
+        self.machine_state()
+    }
+
+    pub fn disassemble(&self, address: u16, count: usize) -> Vec<Instruction> {
+        let mmu = &self.cpu.mmu;
+        let mut instructions = Vec::with_capacity(count);
+        let mut address = address;
+        for _ in 0..count {
+            let instruction = disasm::decode(address, |address| mmu.peek_byte(address));
+            address = address.wrapping_add(instruction.length as u16);
+            instructions.push(instruction);
+        }
+        instructions
+    }
+
+    pub fn start_trace_file(&mut self, path: &str) -> bool {
+        match Tracer::to_file(path) {
+            Ok(tracer) => {
+                self.cpu.tracer = Some(tracer);
+                true
+            },
+            Err(_) => false,
+        }
+    }
+
+    pub fn start_trace_buffer(&mut self, capacity: usize) {
+        self.cpu.tracer = Some(Tracer::to_buffer(capacity));
+    }
+
+    pub fn stop_trace(&mut self) {
+        self.cpu.tracer = None;
+    }
+
+    pub fn trace_lines(&mut self) -> Vec<String> {
+        self.cpu.tracer.as_mut().map(|tracer| tracer.lines()).unwrap_or_default()
+    }
+
+    pub fn screen_buffer(&self) -> Vec<u32> {
+        self.cpu.mmu.ppu.get_screen_buffer()
+    }
+
+    pub fn set_speed(&mut self, speed: f64) {
+        if !speed.is_finite() {
+            return;
+        }
+        self.speed = speed.clamp(MIN_SPEED, MAX_SPEED);
+        self.frame_progress = 0.0;
+        self.cpu.mmu.apu.set_speed(self.speed);
+    }
+
+    // While the debugger holds execution the current frame is returned without emulating, until resume() is called.
+    pub fn render_frame(&mut self) -> FrameOutput {
+        self.cpu.mmu.sync_apu();
+        self.cpu.mmu.apu.clear_samples();
+        if self.remote_debugging || self.debugger.is_stopped() {
+            return self.frame_output();
+        }
+
+        self.frame_progress += self.speed;
+        let frames = self.frame_progress as u32;
+        self.frame_progress -= frames as f64;
+
+        // Only the last frame is returned, so the ones before it don't need to be drawn.
+        for frame in 1..=frames {
+            self.cpu.mmu.ppu.set_rendering_enabled(frame == frames);
+            if !self.run_until_frame() {
+                self.cpu.mmu.ppu.set_rendering_enabled(true);
                 break;
             }
-            drop(ppu);
         }
-    
-        let ppu = self.ppu.lock().unwrap();
-        ppu.get_screen_buffer()
+
+        self.frame_output()
+    }
+
+    fn frame_output(&self) -> FrameOutput {
+        FrameOutput {
+            frame: self.screen_buffer(),
+            stop_reason: self.debugger.stop_reason(),
+        }
     }
-}
\ No newline at end of file
+
+    pub fn run_until_audio(&mut self, sample_frames: usize, buffer_fill: f32) -> AudioSyncOutput {
+        self.cpu.mmu.apu.adjust_rate(buffer_fill);
+
+        while !self.remote_debugging
+            && self.cpu.mmu.apu.buffered_sample_frames() < sample_frames
+            && !self.debugger.is_stopped()
+        {
+            self.step();
+        }
+
+        AudioSyncOutput {
+            frame: self.screen_buffer(),
+            samples: self.take_audio_samples(),
+            stop_reason: self.debugger.stop_reason(),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::savestate::StateError;
+use std::fmt;
+
+pub const MIN_ROM_SIZE: usize = 0x8000; // Two 16 KiB banks, the smallest cartridge ROM
+
+#[derive(Debug)]
+pub enum CoreError {
+    RomTooSmall(usize),                                // ROM is shorter than the smallest cartridge
+    UnsupportedCartridge(u8),                          // Header names a memory bank controller the core doesn't emulate
+    UnknownInstance(u32),                              // No emulator instance has this id
+    InvalidBootRom { expected: usize, actual: usize }, // Boot ROM image doesn't fit the selected model
+    InvalidState(StateError),                          // Save state can't be loaded into this emulator
+    CorruptState,                                      // Emulator couldn't be restored from its own backup after a failed load
+}
+
+impl fmt::Display for CoreError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CoreError::RomTooSmall(size) => {
+                write!(formatter, "The ROM is only {} bytes long, a cartridge has at least {} bytes", size, MIN_ROM_SIZE)
+            },
+            CoreError::UnsupportedCartridge(cartridge_type) => {
+                write!(formatter, "Cartridge type {:02X} is not supported", cartridge_type)
+            },
+            CoreError::UnknownInstance(id) => write!(formatter, "No emulator instance has id {}", id),
+            CoreError::InvalidBootRom { expected, actual } => {
+                write!(formatter, "The boot ROM is {} bytes long, the selected model needs a {} byte image", actual, expected)
+            },
+            CoreError::InvalidState(error) => write!(formatter, "{}", error),
+            CoreError::CorruptState => write!(formatter, "The emulator state is corrupt after a failed state load"),
+        }
+    }
+}
+
+impl std::error::Error for CoreError {}
+
+impl From<StateError> for CoreError {
+    fn from(error: StateError) -> Self {
+        CoreError::InvalidState(error)
+    }
+}
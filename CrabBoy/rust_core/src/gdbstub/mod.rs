@@ -0,0 +1,479 @@
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::debugger::{StopKind, StopReason, Watchpoint};
+use crate::emulator::EMULATOR;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+const FRAME_DURATION: Duration = Duration::from_nanos(16_742_706); // 70224 cycles at 4.194304 MHz
+const PACKET_SIZE: usize = 0x1000;
+const INTERRUPT: u8 = 0x03; // Sent by the client to halt a running target
+
+// The register order matches the first six registers of GDB's z80 target.
+const TARGET_XML: &str = r#"<?xml version="1.0"?>
+<!DOCTYPE target SYSTEM "gdb-target.dtd">
+<target version="1.0">
+  <feature name="org.crabboy.sm83.cpu">
+    <reg name="af" bitsize="16" type="int" regnum="0"/>
+    <reg name="bc" bitsize="16" type="int"/>
+    <reg name="de" bitsize="16" type="int"/>
+    <reg name="hl" bitsize="16" type="int"/>
+    <reg name="sp" bitsize="16" type="data_ptr"/>
+    <reg name="pc" bitsize="16" type="code_ptr"/>
+  </feature>
+</target>
+"#;
+const REGISTER_COUNT: usize = 6;
+
+type SharedEmulator = Weak<Mutex<EMULATOR>>; // Gone once the instance is destroyed
+
+// Serves the GDB remote serial protocol on a loopback port, one client at a time.
+pub struct GdbServer {
+    running: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl GdbServer {
+    pub fn start(port: u16, emulator: SharedEmulator) -> io::Result<Self> {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        listener.set_nonblocking(true)?;
+
+        let running = Arc::new(AtomicBool::new(true));
+        let thread_running = Arc::clone(&running);
+        let thread = thread::spawn(move || serve(listener, thread_running, emulator));
+
+        Ok(GdbServer { running, thread: Some(thread) })
+    }
+}
+
+impl Drop for GdbServer {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn serve(listener: TcpListener, running: Arc<AtomicBool>, emulator: SharedEmulator) {
+    while running.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Ok(mut session) = Session::new(stream, emulator.clone()) {
+                    session.run(&running);
+                }
+            },
+            Err(ref error) if error.kind() == ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+            Err(_) => break,
+        }
+    }
+}
+
+enum Event {
+    Packet(String),
+    Interrupt,
+}
+
+enum Response {
+    Reply(String),
+    Continue, // The stop reply is sent once execution stops
+    Close(Option<String>),
+}
+
+struct Session {
+    stream: TcpStream,
+    emulator: SharedEmulator,
+    input: Vec<u8>,
+    next_frame: Option<Instant>, // Set while the target is running
+    breakpoints: Vec<u16>,       // Added by the client, removed again when it disconnects
+    watchpoints: Vec<(u16, u16)>,
+}
+
+impl Session {
+    fn new(stream: TcpStream, emulator: SharedEmulator) -> io::Result<Self> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(POLL_INTERVAL))?;
+        stream.set_nodelay(true)?;
+        Ok(Session {
+            stream,
+            emulator,
+            input: Vec::new(),
+            next_frame: None,
+            breakpoints: Vec::new(),
+            watchpoints: Vec::new(),
+        })
+    }
+
+    fn run(&mut self, running: &AtomicBool) {
+        self.with_emulator(|emulator| emulator.set_remote_debugging(true));
+
+        let mut buffer = [0u8; PACKET_SIZE];
+        while running.load(Ordering::Relaxed) {
+            match self.stream.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(length) => self.input.extend_from_slice(&buffer[..length]),
+                Err(ref error) if matches!(error.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {},
+                Err(_) => break,
+            }
+
+            if !self.process_input() || !self.run_target() {
+                break;
+            }
+        }
+
+        self.detach();
+    }
+
+    // Returns false when the session has to be closed.
+    fn process_input(&mut self) -> bool {
+        while let Some(event) = self.next_event() {
+            let response = match event {
+                Event::Interrupt if self.next_frame.is_some() => {
+                    self.next_frame = None;
+                    Response::Reply("S02".to_string())
+                },
+                Event::Interrupt => continue,
+                Event::Packet(packet) => {
+                    if self.stream.write_all(b"+").is_err() {
+                        return false;
+                    }
+                    self.handle_packet(&packet)
+                },
+            };
+
+            match response {
+                Response::Reply(reply) => {
+                    if self.send(&reply).is_err() {
+                        return false;
+                    }
+                },
+                Response::Continue => self.next_frame = Some(Instant::now()),
+                Response::Close(reply) => {
+                    if let Some(reply) = reply {
+                        let _ = self.send(&reply);
+                    }
+                    return false;
+                },
+            }
+        }
+        true
+    }
+
+    fn next_event(&mut self) -> Option<Event> {
+        loop {
+            match *self.input.first()? {
+                INTERRUPT => {
+                    self.input.remove(0);
+                    return Some(Event::Interrupt);
+                },
+                b'$' => {
+                    // Wait for the rest of the packet, including its two checksum digits.
+                    let end = self.input.iter().position(|byte| *byte == b'#')?;
+                    if self.input.len() < end + 3 {
+                        return None;
+                    }
+                    let packet = String::from_utf8_lossy(&self.input[1..end]).into_owned();
+                    self.input.drain(..end + 3);
+                    return Some(Event::Packet(packet));
+                },
+                _ => {
+                    // Acknowledgements and line noise.
+                    self.input.remove(0);
+                },
+            }
+        }
+    }
+
+    fn send(&mut self, data: &str) -> io::Result<()> {
+        let checksum = data.bytes().fold(0u8, |sum, byte| sum.wrapping_add(byte));
+        write!(self.stream, "${}#{:02x}", data, checksum)
+    }
+
+    fn with_emulator<R>(&self, action: impl FnOnce(&mut EMULATOR) -> R) -> Option<R> {
+        self.emulator.upgrade().map(|emulator| action(&mut emulator.lock().unwrap()))
+    }
+
+    // Emulates frames at their real-time pace while the target is running. Returns false when the
+    // session has to be closed.
+    fn run_target(&mut self) -> bool {
+        let next_frame = match self.next_frame {
+            Some(next_frame) if Instant::now() >= next_frame => next_frame,
+            _ => return true,
+        };
+
+        let stop = self.with_emulator(|emulator| {
+            if emulator.run_until_frame() {
+                None
+            } else {
+                Some(stop_reply(emulator.stop_reason()))
+            }
+        });
+
+        match stop {
+            Some(None) => {
+                self.next_frame = Some(next_frame + FRAME_DURATION);
+                true
+            },
+            Some(Some(reply)) => {
+                self.next_frame = None;
+                self.send(&reply).is_ok()
+            },
+            None => {
+                let _ = self.send("W00");
+                false
+            },
+        }
+    }
+
+    fn handle_packet(&mut self, packet: &str) -> Response {
+        // Empty packets and unknown commands get an empty reply.
+        if !packet.is_char_boundary(1) {
+            return Response::Reply(String::new());
+        }
+        let (command, arguments) = packet.split_at(1);
+
+        match command {
+            "q" => Response::Reply(query(arguments)),
+            "H" => Response::Reply("OK".to_string()),
+            "k" => Response::Close(None),
+            "D" => Response::Close(Some("OK".to_string())),
+            "Z" | "z" => {
+                let reply = match self.emulator.upgrade() {
+                    Some(emulator) => self.handle_breakpoint(&mut emulator.lock().unwrap(), command == "Z", arguments),
+                    None => "E01".to_string(),
+                };
+                Response::Reply(reply)
+            },
+            "c" => {
+                let address = parse_hex(arguments);
+                let resumed = self.with_emulator(|emulator| {
+                    if let Some(address) = address {
+                        let mut state = emulator.machine_state();
+                        state.pc = address;
+                        emulator.set_registers(&state);
+                    }
+                    emulator.resume();
+                });
+                match resumed {
+                    Some(()) => Response::Continue,
+                    None => Response::Reply("E01".to_string()),
+                }
+            },
+            _ => {
+                let reply = self.with_emulator(|emulator| handle_command(emulator, command, arguments));
+                Response::Reply(reply.unwrap_or_else(|| "E01".to_string()))
+            },
+        }
+    }
+
+    // Handles "type,address,kind" where the kind is the length for watchpoints.
+    fn handle_breakpoint(&mut self, emulator: &mut EMULATOR, insert: bool, arguments: &str) -> String {
+        let mut fields = arguments.split(',');
+        let (kind, address, length) = match (fields.next(), fields.next().and_then(parse_hex), fields.next()) {
+            (Some(kind), Some(address), Some(length)) => (kind, address, parse_hex(length).unwrap_or(1).max(1)),
+            _ => return "E01".to_string(),
+        };
+
+        if kind == "0" || kind == "1" {
+            if insert {
+                emulator.add_remote_breakpoint(address);
+                self.breakpoints.push(address);
+            } else {
+                emulator.remove_remote_breakpoint(address);
+                self.breakpoints.retain(|breakpoint| *breakpoint != address);
+            }
+            return "OK".to_string();
+        }
+
+        let (read, write) = match kind {
+            "2" => (false, true),
+            "3" => (true, false),
+            "4" => (true, true),
+            _ => return String::new(),
+        };
+        let end = address.saturating_add(length - 1);
+        if insert {
+            emulator.add_watchpoint(Watchpoint { start: address, end, read, write, execute: false });
+            self.watchpoints.push((address, end));
+        } else {
+            emulator.remove_watchpoint(address, end);
+            self.watchpoints.retain(|watchpoint| *watchpoint != (address, end));
+        }
+        "OK".to_string()
+    }
+
+    fn detach(&mut self) {
+        let breakpoints = std::mem::take(&mut self.breakpoints);
+        let watchpoints = std::mem::take(&mut self.watchpoints);
+        self.with_emulator(|emulator| {
+            for address in breakpoints {
+                emulator.remove_remote_breakpoint(address);
+            }
+            for (start, end) in watchpoints {
+                emulator.remove_watchpoint(start, end);
+            }
+            emulator.set_remote_debugging(false);
+        });
+    }
+}
+
+fn handle_command(emulator: &mut EMULATOR, command: &str, arguments: &str) -> String {
+    match command {
+        "?" => stop_reply(emulator.stop_reason()),
+        "g" => read_registers(emulator).iter().map(|value| encode_register(*value)).collect(),
+        "G" => {
+            let mut registers = read_registers(emulator);
+            for (index, register) in registers.iter_mut().enumerate() {
+                match arguments.get(index * 4..index * 4 + 4).and_then(decode_register) {
+                    Some(value) => *register = value,
+                    None => return "E01".to_string(),
+                }
+            }
+            write_registers(emulator, registers);
+            "OK".to_string()
+        },
+        "p" => match parse_hex(arguments) {
+            Some(index) if (index as usize) < REGISTER_COUNT => {
+                encode_register(read_registers(emulator)[index as usize])
+            },
+            _ => "E01".to_string(),
+        },
+        "P" => {
+            let register = arguments.split_once('=').and_then(|(index, value)| {
+                Some((parse_hex(index)? as usize, decode_register(value)?))
+            });
+            match register {
+                Some((index, value)) if index < REGISTER_COUNT => {
+                    let mut registers = read_registers(emulator);
+                    registers[index] = value;
+                    write_registers(emulator, registers);
+                    "OK".to_string()
+                },
+                _ => "E01".to_string(),
+            }
+        },
+        "m" => match parse_range(arguments) {
+            Some((address, length)) => {
+                let length = (length as usize).min(PACKET_SIZE / 2);
+                emulator.read_memory(address, length).iter().map(|byte| format!("{:02x}", byte)).collect()
+            },
+            None => "E01".to_string(),
+        },
+        "M" => {
+            let write = arguments.split_once(':').and_then(|(range, data)| {
+                let (address, length) = parse_range(range)?;
+                let bytes = decode_bytes(data)?;
+                (bytes.len() == length as usize).then_some((address, bytes))
+            });
+            match write {
+                Some((address, bytes)) => {
+                    emulator.write_memory(address, &bytes);
+                    "OK".to_string()
+                },
+                None => "E01".to_string(),
+            }
+        },
+        "s" => {
+            if let Some(address) = parse_hex(arguments) {
+                let mut state = emulator.machine_state();
+                state.pc = address;
+                emulator.set_registers(&state);
+            }
+            emulator.step_instruction();
+            stop_reply(emulator.stop_reason())
+        },
+        _ => String::new(),
+    }
+}
+
+fn query(arguments: &str) -> String {
+    if arguments.starts_with("Supported") {
+        format!("PacketSize={:x};qXfer:features:read+;swbreak+;hwbreak+", PACKET_SIZE)
+    } else if arguments == "Attached" {
+        "1".to_string()
+    } else if arguments == "fThreadInfo" {
+        "m1".to_string()
+    } else if arguments == "sThreadInfo" {
+        "l".to_string()
+    } else if arguments == "C" {
+        "QC1".to_string()
+    } else if let Some(range) = arguments.strip_prefix("Xfer:features:read:target.xml:") {
+        match parse_range(range) {
+            Some((offset, length)) => {
+                let offset = (offset as usize).min(TARGET_XML.len());
+                let end = (offset + length as usize).min(TARGET_XML.len());
+                let marker = if end == TARGET_XML.len() { 'l' } else { 'm' };
+                format!("{}{}", marker, &TARGET_XML[offset..end])
+            },
+            None => "E01".to_string(),
+        }
+    } else {
+        String::new()
+    }
+}
+
+fn stop_reply(reason: Option<StopReason>) -> String {
+    match reason {
+        Some(StopReason { kind: StopKind::Breakpoint, .. }) => "T05swbreak:;".to_string(),
+        Some(StopReason { kind: StopKind::ExecuteWatchpoint, .. }) => "T05hwbreak:;".to_string(),
+        Some(StopReason { kind: StopKind::WriteWatchpoint, address }) => format!("T05watch:{:x};", address),
+        Some(StopReason { kind: StopKind::ReadWatchpoint, address }) => format!("T05rwatch:{:x};", address),
+        Some(StopReason { kind: StopKind::Interrupt, .. }) | None => "S05".to_string(),
+    }
+}
+
+// AF, BC, DE, HL, SP and PC.
+fn read_registers(emulator: &EMULATOR) -> [u16; REGISTER_COUNT] {
+    let state = emulator.machine_state();
+    let pair = |high: u8, low: u8| (high as u16) << 8 | low as u16;
+    [pair(state.a, state.f), pair(state.b, state.c), pair(state.d, state.e), pair(state.h, state.l), state.sp, state.pc]
+}
+
+fn write_registers(emulator: &mut EMULATOR, registers: [u16; REGISTER_COUNT]) {
+    let mut state = emulator.machine_state();
+    let [af, bc, de, hl, sp, pc] = registers;
+    [state.a, state.f] = af.to_be_bytes();
+    [state.b, state.c] = bc.to_be_bytes();
+    [state.d, state.e] = de.to_be_bytes();
+    [state.h, state.l] = hl.to_be_bytes();
+    state.sp = sp;
+    state.pc = pc;
+    emulator.set_registers(&state);
+}
+
+// Registers are sent in target byte order, which is little endian.
+fn encode_register(value: u16) -> String {
+    format!("{:02x}{:02x}", value as u8, value >> 8)
+}
+
+fn decode_register(text: &str) -> Option<u16> {
+    let bytes = decode_bytes(text)?;
+    match bytes[..] {
+        [low, high] => Some(u16::from_le_bytes([low, high])),
+        _ => None,
+    }
+}
+
+fn decode_bytes(text: &str) -> Option<Vec<u8>> {
+    if text.len() & 0x01 != 0 {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(text.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+fn parse_hex(text: &str) -> Option<u16> {
+    u16::from_str_radix(text, 16).ok()
+}
+
+// Parses "address,length".
+fn parse_range(text: &str) -> Option<(u16, u16)> {
+    let (address, length) = text.split_once(',')?;
+    Some((parse_hex(address)?, parse_hex(length)?))
+}
@@ -1,12 +1,10 @@
-use std::sync::{Arc, Mutex};
-use crate::cpu::CPU;
+use crate::savestate::{StateError, StateReader, StateWriter};
 
 pub struct JOYPAD {
     buttons: u8,             
     select_direction_keys: bool,
     select_action_keys: bool,    
     previous_buttons: u8,       
-    cpu: Option<Arc<Mutex<CPU>>>,
 }
 
 impl JOYPAD {
@@ -16,12 +14,22 @@ impl JOYPAD {
             select_direction_keys: true, 
             select_action_keys: true,    
             previous_buttons: 0xFF,      
-            cpu: None,                  
         }
     }
 
-    pub fn set_cpu(&mut self, cpu: Arc<Mutex<CPU>>) {
-        self.cpu = Some(cpu);
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        writer.write_u8(self.buttons);
+        writer.write_bool(self.select_direction_keys);
+        writer.write_bool(self.select_action_keys);
+        writer.write_u8(self.previous_buttons);
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.buttons = reader.read_u8()?;
+        self.select_direction_keys = reader.read_bool()?;
+        self.select_action_keys = reader.read_bool()?;
+        self.previous_buttons = reader.read_u8()?;
+        Ok(())
     }
 
     pub fn read_byte(&self) -> u8 {
@@ -65,11 +73,8 @@ impl JOYPAD {
         !self.select_direction_keys || !self.select_action_keys
     }
 
-    pub fn check_for_interrupt(&mut self) {
-        if self.joypad_state_has_changed() && self.is_any_group_active() {
-            if let Some(ref cpu) = self.cpu {
-                cpu.lock().unwrap().request_interrupt(0b00010000);
-            }
-        }
+    // Returns true when a button change should raise the joypad interrupt.
+    pub fn check_for_interrupt(&mut self) -> bool {
+        self.joypad_state_has_changed() && self.is_any_group_active()
     }
 }
\ No newline at end of file
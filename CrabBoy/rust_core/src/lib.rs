@@ -8,6 +8,12 @@ pub mod joypad;
 pub mod bootrom;
 pub mod emulator;
 pub mod cartridge;
+pub mod savestate;
+pub mod rewind;
+pub mod scheduler;
+pub mod debugger;
+pub mod gdbstub;
+pub mod error;
 
 mod api;
 
@@ -1,4 +1,6 @@
 use crate::cartridge::CARTRIDGE;
+use crate::error::CoreError;
+use crate::savestate::{StateError, StateReader, StateWriter};
 
 pub trait MBC: Send {
     fn read_byte(&self, address: u16) -> u8;
@@ -6,12 +8,17 @@ pub trait MBC: Send {
     fn save_ram(&self) -> Option<Vec<u8>> {
         None
     }
+    fn rom_bank(&self) -> u16 {
+        1
+    }
+    fn save_state(&self, writer: &mut StateWriter);
+    fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError>;
 }
 
-pub fn create_mbc(cartridge: CARTRIDGE) -> Box<dyn MBC> {
+pub fn create_mbc(cartridge: CARTRIDGE) -> Result<Box<dyn MBC>, CoreError> {
     let ram_size = cartridge.ram_size.map(|size| size as usize);
     let saved_ram = cartridge.saved_ram.clone();
-    match cartridge.cartridge_type {
+    let mbc: Box<dyn MBC> = match cartridge.cartridge_type {
         0x00 => Box::new(NMBC::new(cartridge.rom, None, false, saved_ram)),      // ROM
         0x08 => Box::new(NMBC::new(cartridge.rom, ram_size, false, saved_ram)),           // ROM + RAM
         0x09 => Box::new(NMBC::new(cartridge.rom, ram_size, true, saved_ram)),            // ROM + RAM + BATTERY
@@ -24,7 +31,31 @@ pub fn create_mbc(cartridge: CARTRIDGE) -> Box<dyn MBC> {
         0x19 => Box::new(MBC5::new(cartridge.rom, None, false, saved_ram)),     // MBC5
         0x1A => Box::new(MBC5::new(cartridge.rom, ram_size, false, saved_ram)),           // MBC5 + RAM
         0x1B => Box::new(MBC5::new(cartridge.rom, ram_size, true, saved_ram)),            // MBC5 + RAM + BATTERY
-        _ => panic!("Unsupported MBC type"),
+        cartridge_type => return Err(CoreError::UnsupportedCartridge(cartridge_type)),
+    };
+    Ok(mbc)
+}
+
+// Banks past the end of a chip mirror its start, as the unused bank lines aren't connected.
+fn wrap(memory: &[u8], offset: usize) -> usize {
+    offset % memory.len()
+}
+
+fn save_ram_state(ram: &Option<Vec<u8>>, writer: &mut StateWriter) {
+    if let Some(ref ram) = ram {
+        writer.write_u32(ram.len() as u32);
+        writer.write_bytes(ram);
+    } else {
+        writer.write_u32(0);
+    }
+}
+
+fn load_ram_state(ram: &mut Option<Vec<u8>>, reader: &mut StateReader) -> Result<(), StateError> {
+    let size = reader.read_u32()? as usize;
+    match ram {
+        Some(ref mut ram) if ram.len() == size => reader.read_bytes(ram),
+        None if size == 0 => Ok(()),
+        _ => Err(StateError::InvalidData),
     }
 }
 
@@ -57,18 +88,19 @@ impl MBC for NMBC {
                 self.rom[address as usize]},
             0xA000..=0xBFFF => {
                 if let Some(ref ram) = self.ram {
-                    ram[(address - 0xA000) as usize]
+                    ram[wrap(ram, (address - 0xA000) as usize)]
                 } else {
                     0
                 }
             }
-            _ => unreachable!(),
+            _ => 0,
         }
     }
 
     fn write_byte(&mut self, address: u16, value: u8) {
-        if let Some(ref mut ram) = self.ram {
-            ram[(address - 0xA000) as usize] = value;
+        if let (0xA000..=0xBFFF, Some(ref mut ram)) = (address, &mut self.ram) {
+            let index = wrap(ram, (address - 0xA000) as usize);
+            ram[index] = value;
         }
     }
 
@@ -79,6 +111,14 @@ impl MBC for NMBC {
             None
         }
     }
+
+    fn save_state(&self, writer: &mut StateWriter) {
+        save_ram_state(&self.ram, writer);
+    }
+
+    fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        load_ram_state(&mut self.ram, reader)
+    }
 }
 
 pub struct MBC1 {
@@ -120,14 +160,14 @@ impl MBC for MBC1 {
             0x4000..=0x7FFF => {
                 let bank_offset = (self.rom_bank as usize) * 0x4000;
                 let bank_address = address as usize - 0x4000;
-                self.rom[bank_offset + bank_address]
+                self.rom[wrap(&self.rom, bank_offset + bank_address)]
             }
             0xA000..=0xBFFF => {
                 if self.ram_enabled {
                     if let Some(ref ram) = self.ram {
                         let bank_offset = (self.ram_bank as usize) * 0x2000;
                         let bank_address = address as usize - 0xA000;
-                        ram[bank_offset + bank_address]
+                        ram[wrap(ram, bank_offset + bank_address)]
                     } else {
                         0
                     }
@@ -159,7 +199,8 @@ impl MBC for MBC1 {
                     if let Some(ref mut ram) = self.ram {
                         let bank_offset = (self.ram_bank as usize) * 0x2000;
                         let bank_address = address as usize - 0xA000;
-                        ram[bank_offset + bank_address] = value;
+                        let index = wrap(ram, bank_offset + bank_address);
+                        ram[index] = value;
                     }
                 }
             }
@@ -174,6 +215,26 @@ impl MBC for MBC1 {
             None
         }
     }
+
+    fn rom_bank(&self) -> u16 {
+        self.rom_bank as u16
+    }
+
+    fn save_state(&self, writer: &mut StateWriter) {
+        writer.write_u8(self.rom_bank);
+        writer.write_u8(self.ram_bank);
+        writer.write_bool(self.mode);
+        writer.write_bool(self.ram_enabled);
+        save_ram_state(&self.ram, writer);
+    }
+
+    fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.rom_bank = reader.read_u8()?;
+        self.ram_bank = reader.read_u8()?;
+        self.mode = reader.read_bool()?;
+        self.ram_enabled = reader.read_bool()?;
+        load_ram_state(&mut self.ram, reader)
+    }
 }
 
 pub struct MBC3 {
@@ -213,14 +274,14 @@ impl MBC for MBC3 {
             0x4000..=0x7FFF => {
                 let bank_offset = (self.rom_bank as usize) * 0x4000;
                 let bank_address = address as usize - 0x4000;
-                self.rom[bank_offset + bank_address]
+                self.rom[wrap(&self.rom, bank_offset + bank_address)]
             },
             0xA000..=0xBFFF => {
                 if self.ram_enabled {
                     if let Some(ref ram) = self.ram {
                         let bank_offset = (self.ram_bank as usize) * 0x2000;
                         let bank_address = address as usize - 0xA000;
-                        ram[bank_offset + bank_address]
+                        ram[wrap(ram, bank_offset + bank_address)]
                     } else {
                         0
                     }
@@ -252,7 +313,8 @@ impl MBC for MBC3 {
                     if let Some(ref mut ram) = self.ram {
                         let bank_offset = (self.ram_bank as usize) * 0x2000;
                         let bank_address = address as usize - 0xA000;
-                        ram[bank_offset + bank_address] = value;
+                        let index = wrap(ram, bank_offset + bank_address);
+                        ram[index] = value;
                     }
                 }
             },
@@ -267,6 +329,24 @@ impl MBC for MBC3 {
             None
         }
     }
+
+    fn rom_bank(&self) -> u16 {
+        self.rom_bank as u16
+    }
+
+    fn save_state(&self, writer: &mut StateWriter) {
+        writer.write_u8(self.rom_bank);
+        writer.write_u8(self.ram_bank);
+        writer.write_bool(self.ram_enabled);
+        save_ram_state(&self.ram, writer);
+    }
+
+    fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.rom_bank = reader.read_u8()?;
+        self.ram_bank = reader.read_u8()?;
+        self.ram_enabled = reader.read_bool()?;
+        load_ram_state(&mut self.ram, reader)
+    }
 }
 
 pub struct MBC5 {
@@ -305,7 +385,7 @@ impl MBC for MBC5 {
             0x4000..=0x7FFF => {
                 let bank_offset = (self.rom_bank as usize) * 0x4000;
                 let address_in_bank = address as usize - 0x4000;
-                self.rom[bank_offset + address_in_bank]
+                self.rom[wrap(&self.rom, bank_offset + address_in_bank)]
             },
 
             0xA000..=0xBFFF => {
@@ -313,7 +393,7 @@ impl MBC for MBC5 {
                     if let Some(ref ram) = self.ram {
                         let bank_offset = (self.ram_bank as usize) * 0x2000;
                         let address_in_bank = address as usize - 0xA000;
-                        ram[bank_offset + address_in_bank]
+                        ram[wrap(ram, bank_offset + address_in_bank)]
                     } else {
                         0
                     }
@@ -348,7 +428,8 @@ impl MBC for MBC5 {
                     if let Some(ref mut ram) = self.ram {
                         let bank_offset = (self.ram_bank as usize) * 0x2000;
                         let address_in_bank = address as usize - 0xA000;
-                        ram[bank_offset + address_in_bank] = value;
+                        let index = wrap(ram, bank_offset + address_in_bank);
+                        ram[index] = value;
                     }
                 }
             }
@@ -363,4 +444,22 @@ impl MBC for MBC5 {
             None
         }
     }
+
+    fn rom_bank(&self) -> u16 {
+        self.rom_bank
+    }
+
+    fn save_state(&self, writer: &mut StateWriter) {
+        writer.write_u16(self.rom_bank);
+        writer.write_u8(self.ram_bank);
+        writer.write_bool(self.ram_enabled);
+        save_ram_state(&self.ram, writer);
+    }
+
+    fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.rom_bank = reader.read_u16()?;
+        self.ram_bank = reader.read_u8()?;
+        self.ram_enabled = reader.read_bool()?;
+        load_ram_state(&mut self.ram, reader)
+    }
 }
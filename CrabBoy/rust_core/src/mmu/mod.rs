@@ -1,21 +1,25 @@
 mod mbc;
 
 use mbc::MBC;
-use crate::ppu::PPU;
-use crate::apu::APU;
+use crate::ppu::{self, PPU};
+use crate::apu::{self, APU};
 use crate::timer::TIMER;
 use crate::joypad::JOYPAD;
-use crate::bootrom::BOOTROM;
+use crate::bootrom::{BOOTROM, Model, PostBootState};
 use crate::cartridge::CARTRIDGE;
+use crate::error::CoreError;
+use crate::savestate::{StateError, StateReader, StateWriter};
+use crate::debugger::{Access, StopReason, Watchpoint};
+use crate::scheduler::{Event, Scheduler};
 
-use std::sync::{Arc, Mutex};
+use std::cell::Cell;
 
 pub struct MMU {
     mbc: Box<dyn MBC>,
-    joypad: Arc<Mutex<JOYPAD>>,
-    timer: Arc<Mutex<TIMER>>,
-    apu: Arc<Mutex<APU>>,
-    ppu: Arc<Mutex<PPU>>,
+    pub joypad: JOYPAD,
+    pub timer: TIMER,
+    pub apu: APU,
+    pub ppu: PPU,
     bootrom: BOOTROM,
     wram: [u8; 8192],     // Work RAM (0xC000 - 0xDFFF)
     hram: [u8; 127],      // High RAM (0xFF80 - 0xFFFE)
@@ -23,66 +27,354 @@ pub struct MMU {
     unusable_area: [u8; 96],
     interrupt_flag: u8,   // Interrupt Flag
     interrupt_enable: u8, // Interrupt Enable Register
+    key1: u8,             // CGB speed switch, bit 7 is the current speed and bit 0 arms a switch
+    model: Model,
+    dma_source: u16,           // Start address of the running OAM DMA transfer
+    dma_position: Option<u8>,  // Next OAM byte the transfer copies, None when no transfer is running
+    ticked_cycles: u16,        // Cycles the other components were advanced by during the current instruction
+    scheduler: Scheduler,
+    timer_synced_at: u64,      // Scheduler time the timer was last brought up to date at
+    apu_synced_at: u64,        // Scheduler time the APU was last brought up to date at
+    watchpoints: Vec<Watchpoint>,
+    watch_hit: Cell<Option<StopReason>>, // First watchpoint hit since the debugger last asked
 }
 
 impl MMU {
-    pub fn new(joypad: Arc<Mutex<JOYPAD>>, timer: Arc<Mutex<TIMER>>, apu: Arc<Mutex<APU>>, ppu: Arc<Mutex<PPU>>, cartridge: CARTRIDGE) -> Self {
-        MMU {
-            mbc: mbc::create_mbc(cartridge),
+    pub fn new(bootrom: BOOTROM, cartridge: CARTRIDGE, model: Model) -> Result<Self, CoreError> {
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(Event::PpuMode, ppu::OAM_SEARCH_CYCLES);
+        scheduler.schedule(Event::FrameSequencer, apu::FRAME_SEQUENCER_PERIOD as u64);
+
+        Ok(MMU {
+            mbc: mbc::create_mbc(cartridge)?,
             wram: [0; 8192],
             hram: [0; 127],
             io: [0; 71],
             unusable_area: [0; 96],
             interrupt_enable: 0,
+            key1: 0,
+            model,
+            dma_source: 0,
+            dma_position: None,
+            ticked_cycles: 0,
+            scheduler,
+            timer_synced_at: 0,
+            apu_synced_at: 0,
             interrupt_flag: 0,
-            bootrom: BOOTROM::new(),
-            joypad,
-            timer,
-            apu,
-            ppu,
+            bootrom,
+            watchpoints: Vec::new(),
+            watch_hit: Cell::new(None),
+            joypad: JOYPAD::new(),
+            timer: TIMER::new(),
+            apu: APU::new(),
+            ppu: PPU::new(),
+        })
+    }
+
+    pub fn has_boot_rom(&self) -> bool {
+        self.bootrom.is_present()
+    }
+
+    pub fn skip_boot(&mut self, state: &PostBootState) {
+        for (address, value) in &state.io {
+            self.poke_byte(*address, *value);
+        }
+        self.set_divider(state.divider);
+        self.bootrom.disable();
+    }
+
+    pub fn reset_divider(&mut self) {
+        self.set_divider(0);
+    }
+
+    fn set_divider(&mut self, divider: u16) {
+        self.sync_timer();
+        self.timer.set_divider(divider);
+        self.schedule_timer_overflow();
+    }
+
+    pub fn request_interrupt(&mut self, interrupt: u8) {
+        self.interrupt_flag |= interrupt;
+    }
+
+    pub fn is_speed_switch_armed(&self) -> bool {
+        self.key1 & 0x01 != 0
+    }
+
+    pub fn switch_speed(&mut self) {
+        self.sync_timer();
+        self.key1 = (self.key1 ^ 0x80) & 0x80;
+        self.schedule_timer_overflow();
+    }
+
+    pub fn is_double_speed(&self) -> bool {
+        self.key1 & 0x80 != 0
+    }
+
+    // The scheduler counts cycles of the normal speed clock, which the PPU and APU run on. In double speed
+    // mode the CPU and timer run twice as fast.
+    fn clock_cycles(&self, cpu_cycles: u64) -> u64 {
+        if self.is_double_speed() { cpu_cycles.div_ceil(2) } else { cpu_cycles }
+    }
+
+    fn cpu_cycles(&self, clock_cycles: u64) -> u64 {
+        if self.is_double_speed() { clock_cycles * 2 } else { clock_cycles }
+    }
+
+    // Advances the other components by the CPU cycles given, firing every event that falls into them.
+    pub fn tick(&mut self, cycles: u16) {
+        self.ticked_cycles += cycles;
+        let time = self.scheduler.now() + self.clock_cycles(cycles as u64);
+        self.run_until(time);
+    }
+
+    fn tick_m_cycle(&mut self) {
+        self.tick(4);
+    }
+
+    fn run_until(&mut self, time: u64) {
+        while let Some(event) = self.scheduler.next_event_until(time) {
+            self.run_event(event);
+        }
+    }
+
+    fn run_event(&mut self, event: Event) {
+        match event {
+            Event::PpuMode => {
+                let cycles = self.ppu.advance_mode();
+                self.interrupt_flag |= self.ppu.take_interrupt_requests();
+                self.scheduler.schedule(Event::PpuMode, cycles);
+            },
+            Event::TimerOverflow => {
+                self.sync_timer();
+                self.schedule_timer_overflow();
+            },
+            Event::SerialTransfer => {
+                // Without a link partner every bit shifted in reads as 1.
+                self.io[0] = 0xFF;  // SB
+                self.io[1] &= 0x7F; // SC, the transfer is done
+                self.interrupt_flag |= 0b00001000;
+            },
+            Event::FrameSequencer => {
+                self.sync_apu();
+                if self.apu.is_powered_on() {
+                    self.apu.step_frame_sequencer();
+                }
+                self.scheduler.schedule(Event::FrameSequencer, apu::FRAME_SEQUENCER_PERIOD as u64);
+            },
+            Event::DmaTransfer => self.transfer_dma_byte(),
+        }
+    }
+
+    // In STOP mode the LCD and timer are frozen, so their events are held back, while the APU keeps producing samples.
+    pub fn tick_stopped(&mut self, cycles: u16) {
+        let clock_cycles = self.clock_cycles(cycles as u64);
+        self.sync_timer();
+        self.timer_synced_at += clock_cycles;
+        for event in [Event::PpuMode, Event::TimerOverflow, Event::SerialTransfer, Event::DmaTransfer] {
+            self.scheduler.postpone(event, clock_cycles);
+        }
+        self.run_until(self.scheduler.now() + clock_cycles);
+    }
+
+    // Ticks the internal M-cycles of an instruction that its memory accesses did not already cover.
+    pub fn finish_instruction(&mut self, cycles: u16) {
+        self.tick(cycles.saturating_sub(self.ticked_cycles));
+        self.ticked_cycles = 0;
+    }
+
+    // The timer is only brought up to date when its registers are written or it overflows.
+    fn sync_timer(&mut self) {
+        let elapsed = self.scheduler.now() - self.timer_synced_at;
+        self.timer.advance(self.cpu_cycles(elapsed));
+        self.timer_synced_at = self.scheduler.now();
+        self.interrupt_flag |= self.timer.take_interrupt_requests();
+    }
+
+    fn schedule_timer_overflow(&mut self) {
+        match self.timer.cycles_until_overflow() {
+            Some(cycles) => self.scheduler.schedule(Event::TimerOverflow, self.clock_cycles(cycles)),
+            None => self.scheduler.cancel(Event::TimerOverflow),
         }
     }
 
-    pub fn fetch_instruction(&self, pc: u16) -> u8 {
-        self.read_byte(pc)
+    // The APU is brought up to date before its registers are written, on frame sequencer steps and before
+    // its samples are taken.
+    pub fn sync_apu(&mut self) {
+        let elapsed = self.scheduler.now() - self.apu_synced_at;
+        self.apu.tick(elapsed as u32);
+        self.apu_synced_at = self.scheduler.now();
     }
 
-    pub fn fetch_u8(&self, address: u16) -> u8 {
-        self.read_byte(address)
+    fn write_apu(&mut self, address: u16, value: u8) {
+        self.sync_apu();
+        let powered_on = self.apu.is_powered_on();
+        self.apu.write_byte(address, value);
+        // Powering on restarts the frame sequencer.
+        if !powered_on && self.apu.is_powered_on() {
+            self.scheduler.schedule(Event::FrameSequencer, apu::FRAME_SEQUENCER_PERIOD as u64);
+        }
+    }
+
+    // OAM DMA copies one byte per M-cycle, starting with the M-cycle after the write to 0xFF46.
+    fn transfer_dma_byte(&mut self) {
+        if let Some(position) = self.dma_position {
+            let value = self.peek_byte(self.dma_source + position as u16);
+            self.ppu.write_byte(0xFE00 + position as u16, value);
+            self.dma_position = if position < 0x9F { Some(position + 1) } else { None };
+        }
+        if self.dma_position.is_some() {
+            let cycles = self.clock_cycles(4);
+            self.scheduler.schedule(Event::DmaTransfer, cycles);
+        }
+    }
+
+    fn is_dma_blocking(&self, address: u16) -> bool {
+        self.dma_position.is_some() && (0xFE00..=0xFE9F).contains(&address)
+    }
+
+    // Each access made by the CPU takes one M-cycle, during which the other components keep running.
+    pub fn fetch_instruction(&mut self, pc: u16) -> u8 {
+        self.tick_m_cycle();
+        self.peek_byte(pc)
+    }
+
+    // Operand fetches are not data accesses, so they don't trigger read watchpoints.
+    pub fn fetch_u8(&mut self, address: u16) -> u8 {
+        self.tick_m_cycle();
+        self.peek_byte(address)
     }
 
-    pub fn fetch_i8(&self, address: u16) -> i8 {
-        self.read_byte(address) as i8
+    pub fn fetch_i8(&mut self, address: u16) -> i8 {
+        self.fetch_u8(address) as i8
+    }
+
+    // 16-bit values are accessed low byte first, one M-cycle per byte.
+    pub fn fetch_u16(&mut self, address: u16) -> u16 {
+        let low = self.fetch_u8(address);
+        u16::from_le_bytes([low, self.fetch_u8(address.wrapping_add(1))])
+    }
+
+    pub fn read_u16(&mut self, address: u16) -> u16 {
+        let low = self.read_byte(address);
+        u16::from_le_bytes([low, self.read_byte(address.wrapping_add(1))])
     }
 
     pub fn save_ram(&self) -> Option<Vec<u8>> {
         self.mbc.save_ram()
     }
 
-    pub fn read_byte(&self, address: u16) -> u8 {
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        writer.write_bytes(&self.wram);
+        writer.write_bytes(&self.hram);
+        writer.write_bytes(&self.io);
+        writer.write_bytes(&self.unusable_area);
+        writer.write_u8(self.interrupt_flag);
+        writer.write_u8(self.interrupt_enable);
+        writer.write_u8(self.key1);
+        writer.write_u16(self.dma_source);
+        writer.write_bool(self.dma_position.is_some());
+        writer.write_u8(self.dma_position.unwrap_or(0));
+        self.scheduler.save_state(writer);
+        writer.write_u64(self.timer_synced_at);
+        writer.write_u64(self.apu_synced_at);
+        writer.write_bool(self.bootrom.boot_enabled);
+        self.mbc.save_state(writer);
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        reader.read_bytes(&mut self.wram)?;
+        reader.read_bytes(&mut self.hram)?;
+        reader.read_bytes(&mut self.io)?;
+        reader.read_bytes(&mut self.unusable_area)?;
+        self.interrupt_flag = reader.read_u8()?;
+        self.interrupt_enable = reader.read_u8()?;
+        self.key1 = reader.read_u8()?;
+        self.dma_source = reader.read_u16()?;
+        let dma_running = reader.read_bool()?;
+        let dma_position = reader.read_u8()?;
+        if dma_position > 0x9F {
+            return Err(StateError::InvalidData);
+        }
+        self.dma_position = if dma_running { Some(dma_position) } else { None };
+        self.scheduler.load_state(reader)?;
+        self.timer_synced_at = reader.read_u64()?;
+        self.apu_synced_at = reader.read_u64()?;
+        if self.timer_synced_at > self.scheduler.now() || self.apu_synced_at > self.scheduler.now() {
+            return Err(StateError::InvalidData);
+        }
+        self.bootrom.boot_enabled = reader.read_bool()?;
+        self.mbc.load_state(reader)
+    }
+
+    pub fn set_watchpoints(&mut self, watchpoints: Vec<Watchpoint>) {
+        self.watchpoints = watchpoints;
+    }
+
+    pub fn take_watch_hit(&self) -> Option<StopReason> {
+        self.watch_hit.take()
+    }
+
+    fn check_watchpoints(&self, address: u16, access: Access) {
+        if self.watchpoints.is_empty() {
+            return;
+        }
+
+        let hit = self.watchpoints.iter().find_map(|watchpoint| watchpoint.check(address, access));
+        if let Some(reason) = hit {
+            let previous = self.watch_hit.take();
+            self.watch_hit.set(previous.or(Some(reason)));
+        }
+    }
+
+    pub fn rom_bank(&self, address: u16) -> u16 {
+        match address {
+            0x4000..=0x7FFF => self.mbc.rom_bank(),
+            _ => 0,
+        }
+    }
+
+    pub fn read_byte(&mut self, address: u16) -> u8 {
+        self.tick_m_cycle();
+        self.check_watchpoints(address, Access::Read);
+        // OAM is busy while DMA runs, the CPU reads open bus instead.
+        if self.is_dma_blocking(address) {
+            return 0xFF;
+        }
+        self.peek_byte(address)
+    }
+
+    // Reads without taking time or notifying the debugger, for accesses that are not made by the emulated program.
+    pub fn peek_byte(&self, address: u16) -> u8 {
         match address {
             0x0000..=0x7FFF => {
-                if address <= 0x00FF && self.bootrom.boot_enabled {
+                if self.bootrom.is_mapped(address) {
                     self.bootrom.read_byte(address)
                 } else {
                     self.mbc.read_byte(address)
                 }
             }
-            0x8000..=0x9FFF => self.ppu.lock().unwrap().read_byte(address),
+            0x8000..=0x9FFF => self.ppu.read_byte(address),
             0xA000..=0xBFFF => self.mbc.read_byte(address),
             0xC000..=0xDFFF => self.wram[address as usize - 0xC000],
             0xE000..=0xFDFF => self.wram[address as usize - 0xE000],
-            0xFE00..=0xFE9F => self.ppu.lock().unwrap().read_byte(address),
+            0xFE00..=0xFE9F => self.ppu.read_byte(address),
             0xFEA0..=0xFEFF => self.unusable_area[address as usize - 0xFEA0],
-            0xFF00 => self.joypad.lock().unwrap().read_byte(),
+            0xFF00 => self.joypad.read_byte(),
             0xFF01..=0xFF03 => self.io[address as usize - 0xFF01],
-            0xFF04..=0xFF07 => self.timer.lock().unwrap().read_byte(address),
+            0xFF04..=0xFF07 => {
+                // Reads see the timer as it would be by now, without bringing it up to date.
+                let mut timer = self.timer.clone();
+                timer.advance(self.cpu_cycles(self.scheduler.now() - self.timer_synced_at));
+                timer.read_byte(address)
+            },
             0xFF08..=0xFF0E => self.io[address as usize - 0xFF08 + 3],
             0xFF0F => self.interrupt_flag,
-            0xFF10..=0xFF26 => self.apu.lock().unwrap().read_byte(address),
+            0xFF10..=0xFF26 => self.apu.read_byte(address),
             0xFF27..=0xFF2F => self.io[address as usize - 0xFF27 + 10],
-            0xFF30..=0xFF3F => self.apu.lock().unwrap().read_byte(address),
-            0xFF40..=0xFF4B => self.ppu.lock().unwrap().read_byte(address),
+            0xFF30..=0xFF3F => self.apu.read_byte(address),
+            0xFF40..=0xFF4B => self.ppu.read_byte(address),
+            0xFF4D if self.model == Model::Cgb => self.key1 | 0x7E,
             0xFF4C..=0xFF7F => self.io[address as usize - 0xFF4C + 19],
             0xFF80..=0xFFFE => self.hram[address as usize - 0xFF80],
             0xFFFF => self.interrupt_enable,
@@ -90,28 +382,61 @@ impl MMU {
     }
 
     pub fn write_byte(&mut self, address: u16, value: u8) {
+        self.tick_m_cycle();
+        self.check_watchpoints(address, Access::Write);
+        if self.is_dma_blocking(address) {
+            return;
+        }
+        self.poke_byte(address, value);
+    }
+
+    // Writes without taking time or notifying the debugger, for accesses that are not made by the emulated program.
+    pub fn poke_byte(&mut self, address: u16, value: u8) {
         match address {
             0x0000..=0x7FFF => self.mbc.write_byte(address, value),
-            0x8000..=0x9FFF => self.ppu.lock().unwrap().write_byte(address, value),
+            0x8000..=0x9FFF => self.ppu.write_byte(address, value),
             0xA000..=0xBFFF => self.mbc.write_byte(address, value),
             0xC000..=0xDFFF => self.wram[address as usize - 0xC000] = value,
             0xE000..=0xFDFF => self.wram[address as usize - 0xE000] = value,
-            0xFE00..=0xFE9F => self.ppu.lock().unwrap().write_byte(address, value),
+            0xFE00..=0xFE9F => self.ppu.write_byte(address, value),
             0xFEA0..=0xFEFF => self.unusable_area[address as usize - 0xFEA0] = value,
-            0xFF00 => self.joypad.lock().unwrap().write_byte(value),
+            0xFF00 => self.joypad.write_byte(value),
+            0xFF02 => {
+                self.io[1] = value;
+                if value & 0x81 == 0x81 {
+                    // The internal clock shifts a bit every 512 CPU cycles, or every 16 with the CGB fast clock.
+                    let bit_cycles = if self.model == Model::Cgb && value & 0x02 != 0 { 16 } else { 512 };
+                    let cycles = self.clock_cycles(8 * bit_cycles);
+                    self.scheduler.schedule(Event::SerialTransfer, cycles);
+                } else {
+                    self.scheduler.cancel(Event::SerialTransfer);
+                }
+            },
             0xFF01..=0xFF03 => self.io[address as usize - 0xFF01] = value,
-            0xFF04..=0xFF07 => self.timer.lock().unwrap().write_byte(address, value),
+            0xFF04..=0xFF07 => {
+                self.sync_timer();
+                self.timer.write_byte(address, value);
+                self.schedule_timer_overflow();
+            },
             0xFF08..=0xFF0E => self.io[address as usize - 0xFF08 + 3] = value,
             0xFF0F => self.interrupt_flag = value,
-            0xFF10..=0xFF26 => self.apu.lock().unwrap().write_byte(address, value),
+            0xFF10..=0xFF26 => self.write_apu(address, value),
             0xFF27..=0xFF2F => self.io[address as usize - 0xFF27 + 10] = value,
-            0xFF30..=0xFF3F => self.apu.lock().unwrap().write_byte(address, value),
-            0xFF40..=0xFF4B => self.ppu.lock().unwrap().write_byte(address, value),
+            0xFF30..=0xFF3F => self.write_apu(address, value),
+            0xFF46 => {
+                self.ppu.write_byte(address, value);
+                self.dma_source = (value as u16) << 8;
+                self.dma_position = Some(0);
+                let cycles = self.clock_cycles(4);
+                self.scheduler.schedule(Event::DmaTransfer, cycles);
+            },
+            0xFF40..=0xFF4B => self.ppu.write_byte(address, value),
             0xFF50 => {
                 if value == 0x01 {
                     self.bootrom.disable();
                 }
             }
+            0xFF4D if self.model == Model::Cgb => self.key1 = (self.key1 & 0x80) | (value & 0x01),
             0xFF4C..=0xFF7F => self.io[address as usize - 0xFF4C + 19] = value,
             0xFF80..=0xFFFE => self.hram[address as usize - 0xFF80] = value,
             0xFFFF => self.interrupt_enable = value,
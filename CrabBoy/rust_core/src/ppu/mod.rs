@@ -1,7 +1,36 @@
-use std::sync::{Arc, Mutex};
+use crate::savestate::{StateError, StateReader, StateWriter};
 
-use crate::mmu::MMU;
-use crate::cpu::CPU;
+pub const OAM_SEARCH_CYCLES: u64 = 80; // Length of mode 2, which every visible line starts with
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    HBlank,    // Mode 0
+    VBlank,    // Mode 1
+    OamSearch, // Mode 2
+    Transfer,  // Mode 3, pixels are sent to the LCD
+}
+
+impl Mode {
+    // The number STAT reports the mode as.
+    fn id(self) -> u8 {
+        match self {
+            Mode::HBlank => 0,
+            Mode::VBlank => 1,
+            Mode::OamSearch => 2,
+            Mode::Transfer => 3,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Mode::HBlank),
+            1 => Some(Mode::VBlank),
+            2 => Some(Mode::OamSearch),
+            3 => Some(Mode::Transfer),
+            _ => None,
+        }
+    }
+}
 
 pub struct PPU {
     lcdc: u8,         // LCD Control (0xFF40)
@@ -18,13 +47,11 @@ pub struct PPU {
     wx: u8,           // Window X Position minus 7 (0xFF4B)
     vram: [u8; 8192], // Video RAM (0x8000 - 0x9FFF)
     oam: [u8; 160],   // Object Attribute Memory (0xFE00 - 0xFE9F)
-    dma_transfer_enabled: bool,
     screen_buffer: [[u32; 160]; 144], // Screen buffer
-    mmu: Option<Arc<Mutex<MMU>>>,
-    cpu: Option<Arc<Mutex<CPU>>>,
-    mode: u8,         
-    cycles: u32,
+    interrupt_requests: u8, // Interrupts raised since the MMU last collected them
+    mode: Mode,
     frame_ready: bool,
+    rendering_enabled: bool, // Cleared for frames that are emulated but never shown
 }
 
 impl PPU {
@@ -44,14 +71,41 @@ impl PPU {
             wx: 0,
             vram: [0; 8192],
             oam: [0; 160],
-            dma_transfer_enabled: false,
             screen_buffer: [[0; 160]; 144],
-            mmu: None,
-            cpu: None,
-            mode: 2, 
-            cycles: 0,
+            interrupt_requests: 0,
+            mode: Mode::OamSearch,
             frame_ready: false,
+            rendering_enabled: true,
+        }
+    }
+
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        let registers = [
+            self.lcdc, self.stat, self.scy, self.scx, self.ly, self.lyc,
+            self.dma, self.bgp, self.obp0, self.obp1, self.wy, self.wx,
+        ];
+        writer.write_bytes(&registers);
+        writer.write_bytes(&self.vram);
+        writer.write_bytes(&self.oam);
+        writer.write_u8(self.mode.id());
+        writer.write_bool(self.frame_ready);
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        let mut registers = [0; 12];
+        reader.read_bytes(&mut registers)?;
+        [
+            self.lcdc, self.stat, self.scy, self.scx, self.ly, self.lyc,
+            self.dma, self.bgp, self.obp0, self.obp1, self.wy, self.wx,
+        ] = registers;
+        reader.read_bytes(&mut self.vram)?;
+        reader.read_bytes(&mut self.oam)?;
+        self.mode = Mode::from_id(reader.read_u8()?).ok_or(StateError::InvalidData)?;
+        if self.ly > 153 {
+            return Err(StateError::InvalidData);
         }
+        self.frame_ready = reader.read_bool()?;
+        Ok(())
     }
 
     pub fn read_byte(&self, address: u16) -> u8 {
@@ -70,7 +124,7 @@ impl PPU {
             0xFF49 => self.obp1,
             0xFF4A => self.wy,
             0xFF4B => self.wx,
-            _ => panic!("Attempted to read from an invalid memory address: {:04X}", address),
+            _ => 0xFF,
         }
     }
 
@@ -84,90 +138,75 @@ impl PPU {
             0xFF43 => self.scx = value,
             0xFF44 => self.ly = value,
             0xFF45 => self.lyc = value,
-            0xFF46 => {
-                self.dma = value;
-                self.dma_transfer_enabled = true;
-            },
+            0xFF46 => self.dma = value,
             0xFF47 => self.bgp = value,
             0xFF48 => self.obp0 = value,
             0xFF49 => self.obp1 = value,
             0xFF4A => self.wy = value,
             0xFF4B => self.wx = value,
-            _ => panic!("Attempted to write to an invalid memory address: {:04X}", address),
+            _ => (),
         }
     }
 
-    pub fn tick(&mut self, cycles: u16) {
-        self.cycles += cycles as u32;
-    
+    // Moves the PPU to its next mode, returns the cycles until the mode after that.
+    pub fn advance_mode(&mut self) -> u64 {
         match self.mode {
-            2 => { // OAM Search
-                if self.cycles >= 80 {
-                    self.cycles -= 80;
-                    self.stat |= 0b11;
-                    self.mode = 3;
+            Mode::OamSearch => {
+                self.stat |= 0b11;
+                self.mode = Mode::Transfer;
+                170
+            },
+            Mode::Transfer => {
+                if self.stat & (1 << 3) != 0 {
+                    self.interrupt_requests |= 0b00000010;
                 }
-            }
-            3 => { // Access VRAM
-                if self.cycles >= 170 {
-                    self.cycles -= 170;
-
-                    if self.stat & (1 << 3) != 0 {
-                        if let Some(cpu) = &self.cpu {
-                            cpu.lock().unwrap().request_interrupt(0b00000010);
-                        }
-                    }
-                    let ly_coincidence = self.ly == self.lyc;
-                    if ly_coincidence {
-                        self.stat |= 1 << 2;
-                        if self.stat & (1 << 6) != 0 {
-                            if let Some(cpu) = &self.cpu {
-                                cpu.lock().unwrap().request_interrupt(0b00000010);
-                            }
-                        }
-                    } else {
-                        self.stat &= !(1 << 2);
+                let ly_coincidence = self.ly == self.lyc;
+                if ly_coincidence {
+                    self.stat |= 1 << 2;
+                    if self.stat & (1 << 6) != 0 {
+                        self.interrupt_requests |= 0b00000010;
                     }
-    
-                    self.stat &= !0b11;
-                    self.mode = 0;
+                } else {
+                    self.stat &= !(1 << 2);
                 }
-            }
-            0 => { // H-Blank
-                if self.cycles >= 206 {
-                    self.cycles -= 206;
+
+                self.stat &= !0b11;
+                self.mode = Mode::HBlank;
+                206
+            },
+            Mode::HBlank => {
+                if self.rendering_enabled {
                     self.render_scanline();
-                    self.ly += 1;
+                }
+                self.ly += 1;
 
-                    if self.ly == 144 {
-                        self.stat |= 0b01;
-                        self.stat &= !(1 << 1);
-                        self.mode = 1;
-                        if let Some(cpu) = &self.cpu {
-                            cpu.lock().unwrap().request_interrupt(0b00000001);
-                        }
-                    } else {
-                        self.stat |= 0b10;
-                        self.stat &= !0b01;
-                        self.mode = 2;
-                    }
+                if self.ly == 144 {
+                    self.stat |= 0b01;
+                    self.stat &= !(1 << 1);
+                    self.mode = Mode::VBlank;
+                    self.interrupt_requests |= 0b00000001;
+                    456
+                } else {
+                    self.stat |= 0b10;
+                    self.stat &= !0b01;
+                    self.mode = Mode::OamSearch;
+                    OAM_SEARCH_CYCLES
                 }
-            }
-            1 => { // V-Blank
-                if self.cycles >= 456 {
-                    self.cycles -= 456;
-                    self.ly += 1;
-    
-                    if self.ly > 153 {
-                        self.ly = 0;
-                        self.stat |= 0b10;
-                        self.stat &= !0b01;
-                        self.mode = 2;
-                        self.frame_ready = true;
-                    }
+            },
+            Mode::VBlank => {
+                self.ly += 1;
+
+                if self.ly > 153 {
+                    self.ly = 0;
+                    self.stat |= 0b10;
+                    self.stat &= !0b01;
+                    self.mode = Mode::OamSearch;
+                    self.frame_ready = true;
+                    OAM_SEARCH_CYCLES
+                } else {
+                    456
                 }
-            }
-            _ => panic!("Unknown mode!"),
+            },
         }
     }
 
@@ -178,6 +217,18 @@ impl PPU {
     pub fn reset_frame_ready(&mut self) {
         self.frame_ready = false;
     }
+
+    pub fn set_rendering_enabled(&mut self, enabled: bool) {
+        self.rendering_enabled = enabled;
+    }
+
+    pub fn ly(&self) -> u8 {
+        self.ly
+    }
+
+    pub fn mode(&self) -> u8 {
+        self.mode.id()
+    }
     
     fn is_display_enabled(&self) -> bool {
         (self.lcdc & 0x80) != 0
@@ -187,12 +238,8 @@ impl PPU {
         self.lcdc & 0x01 != 0
     }
 
-    pub fn set_mmu(&mut self, mmu: Arc<Mutex<MMU>>) {
-        self.mmu = Some(mmu);
-    }
-
-    pub fn set_cpu(&mut self, cpu: Arc<Mutex<CPU>>) {
-        self.cpu = Some(cpu);
+    pub fn take_interrupt_requests(&mut self) -> u8 {
+        std::mem::take(&mut self.interrupt_requests)
     }
 
     pub fn get_screen_buffer(&self) -> Vec<u32> {
@@ -206,21 +253,6 @@ impl PPU {
         buffer
     }
 
-    pub fn dma_transfer(&mut self) {
-        if self.dma_transfer_enabled {
-            let source_address = (self.dma as u16) << 8;
-            if let Some(mmu) = &self.mmu {
-                for i in 0..0xA0 {
-                    let byte = mmu.lock().unwrap().read_byte(source_address + i as u16);
-                    self.oam[i] = byte;
-                }
-            } else {
-                panic!("PPU has no reference to MMU.");
-            }
-        }
-        self.dma_transfer_enabled = false;
-    }
-
     fn render_scanline(&mut self) {
         if !self.is_display_enabled() {
             return;
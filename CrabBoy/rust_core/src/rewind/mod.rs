@@ -0,0 +1,254 @@
+use std::collections::VecDeque;
+
+pub const DEFAULT_MEMORY_BUDGET: usize = 8 * 1024 * 1024;
+pub const DEFAULT_FRAMES_PER_SNAPSHOT: u32 = 4;
+const MIN_ZERO_RUN: usize = 4; // Shorter runs of unchanged bytes are cheaper to keep inside a literal
+
+// Keeps the newest snapshot as a whole and every older one as a compressed delta against the snapshot
+// taken right after it. Rewinding walks back from the newest state, and the oldest deltas can be dropped
+// to stay within the memory budget without breaking the chain.
+pub struct RewindBuffer {
+    memory_budget: usize,       // Upper bound for the bytes held by the snapshots, 0 disables rewinding
+    frames_per_snapshot: u32,
+    frames_since_snapshot: u32, // Frames emulated since the newest snapshot was taken
+    latest: Option<Vec<u8>>,
+    deltas: VecDeque<Vec<u8>>,  // Oldest first
+    used_memory: usize,
+}
+
+impl RewindBuffer {
+    pub fn new(memory_budget: usize, frames_per_snapshot: u32) -> Self {
+        RewindBuffer {
+            memory_budget,
+            frames_per_snapshot: frames_per_snapshot.max(1),
+            frames_since_snapshot: 0,
+            latest: None,
+            deltas: VecDeque::new(),
+            used_memory: 0,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.frames_since_snapshot = 0;
+        self.latest = None;
+        self.deltas.clear();
+        self.used_memory = 0;
+    }
+
+    // Called once per emulated frame, returns true when a snapshot should be pushed.
+    pub fn frame_completed(&mut self) -> bool {
+        if self.memory_budget == 0 {
+            return false;
+        }
+        self.frames_since_snapshot += 1;
+        self.latest.is_none() || self.frames_since_snapshot >= self.frames_per_snapshot
+    }
+
+    pub fn push(&mut self, state: Vec<u8>) {
+        if let Some(previous) = self.latest.take() {
+            let delta = encode_delta(&previous, &state);
+            self.used_memory += delta.len();
+            self.used_memory -= previous.len();
+            self.deltas.push_back(delta);
+        }
+        self.used_memory += state.len();
+        self.latest = Some(state);
+        self.frames_since_snapshot = 0;
+
+        while self.used_memory > self.memory_budget {
+            match self.deltas.pop_front() {
+                Some(delta) => self.used_memory -= delta.len(),
+                None => break,
+            }
+        }
+    }
+
+    // Returns the snapshot to restore together with the number of frames it lies in the past. Snapshots are only
+    // taken every frames_per_snapshot frames, so this can go back up to one interval further than asked for.
+    pub fn rewind(&mut self, frames: u32) -> Option<(Vec<u8>, u32)> {
+        if frames == 0 {
+            return None;
+        }
+
+        let mut rewound = self.frames_since_snapshot;
+        while rewound < frames {
+            let delta = match self.deltas.pop_back() {
+                Some(delta) => delta,
+                None => break,
+            };
+            let latest = self.latest.take()?;
+            let previous = decode_delta(&delta, &latest);
+            self.used_memory -= delta.len() + latest.len();
+            self.used_memory += previous.len();
+            self.latest = Some(previous);
+            rewound += self.frames_per_snapshot;
+        }
+
+        if rewound == 0 {
+            return None;
+        }
+        self.frames_since_snapshot = 0;
+        self.latest.clone().map(|state| (state, rewound))
+    }
+}
+
+// Delta layout: the length of the older state, followed by pairs of (unchanged byte count, literal length)
+// with the literal bytes holding the XOR of both states.
+fn encode_delta(older: &[u8], newer: &[u8]) -> Vec<u8> {
+    let difference: Vec<u8> = older
+        .iter()
+        .enumerate()
+        .map(|(index, byte)| byte ^ newer.get(index).copied().unwrap_or(0))
+        .collect();
+
+    let mut delta = Vec::new();
+    write_varint(&mut delta, older.len());
+
+    let mut position = 0;
+    while position < difference.len() {
+        let zero_start = position;
+        while position < difference.len() && difference[position] == 0 {
+            position += 1;
+        }
+        let zero_run = position - zero_start;
+
+        let literal_start = position;
+        while position < difference.len() && !starts_zero_run(&difference[position..]) {
+            position += 1;
+        }
+
+        write_varint(&mut delta, zero_run);
+        write_varint(&mut delta, position - literal_start);
+        delta.extend_from_slice(&difference[literal_start..position]);
+    }
+    delta
+}
+
+fn starts_zero_run(bytes: &[u8]) -> bool {
+    bytes.iter().take(MIN_ZERO_RUN).all(|byte| *byte == 0)
+}
+
+fn decode_delta(delta: &[u8], newer: &[u8]) -> Vec<u8> {
+    let mut position = 0;
+    let length = read_varint(delta, &mut position);
+    let mut older: Vec<u8> = (0..length).map(|index| newer.get(index).copied().unwrap_or(0)).collect();
+
+    let mut offset = 0;
+    while position < delta.len() {
+        offset += read_varint(delta, &mut position);
+        let literal_length = read_varint(delta, &mut position);
+        for byte in &delta[position..position + literal_length] {
+            older[offset] ^= byte;
+            offset += 1;
+        }
+        position += literal_length;
+    }
+    older
+}
+
+fn write_varint(output: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        output.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+fn read_varint(input: &[u8], position: &mut usize) -> usize {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = input[*position];
+        *position += 1;
+        value |= ((byte & 0x7F) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return value;
+        }
+        shift += 7;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(seed: u8, length: usize) -> Vec<u8> {
+        (0..length).map(|index| seed.wrapping_add(index as u8)).collect()
+    }
+
+    fn round_trip(older: &[u8], newer: &[u8]) {
+        let delta = encode_delta(older, newer);
+        assert_eq!(decode_delta(&delta, newer), older);
+    }
+
+    #[test]
+    fn delta_of_equal_lengths_round_trips() {
+        let older = state(0, 64);
+        let mut newer = older.clone();
+        newer[3] ^= 0xFF;
+        newer[40..44].copy_from_slice(&[1, 2, 3, 4]);
+        round_trip(&older, &newer);
+        round_trip(&older, &older);
+        round_trip(&older, &state(1, 64));
+        round_trip(&[], &[]);
+    }
+
+    #[test]
+    fn delta_of_unchanged_states_is_small() {
+        let older = state(0, 1000);
+        assert!(encode_delta(&older, &older).len() < 8);
+    }
+
+    #[test]
+    fn delta_of_different_lengths_round_trips() {
+        round_trip(&state(0, 10), &state(0, 300));
+        round_trip(&state(0, 300), &state(5, 10));
+        round_trip(&state(7, 200), &[]);
+        round_trip(&[], &state(7, 200));
+    }
+
+    #[test]
+    fn rewind_walks_back_through_the_snapshots() {
+        let mut buffer = RewindBuffer::new(DEFAULT_MEMORY_BUDGET, 2);
+        for seed in 0..5 {
+            assert!(buffer.frame_completed());
+            buffer.push(state(seed, 100));
+            assert!(!buffer.frame_completed());
+        }
+
+        // One frame since the last snapshot, so the next older one lies three frames back.
+        assert_eq!(buffer.rewind(3), Some((state(3, 100), 3)));
+        assert_eq!(buffer.rewind(4), Some((state(1, 100), 4)));
+    }
+
+    #[test]
+    fn rewind_by_zero_frames_does_nothing() {
+        let mut buffer = RewindBuffer::new(DEFAULT_MEMORY_BUDGET, 1);
+        buffer.frame_completed();
+        buffer.push(state(0, 100));
+        buffer.frame_completed();
+        assert_eq!(buffer.rewind(0), None);
+        assert_eq!(buffer.rewind(1), Some((state(0, 100), 1)));
+    }
+
+    #[test]
+    fn oldest_snapshots_are_dropped_to_fit_the_budget() {
+        let budget = 350;
+        let mut buffer = RewindBuffer::new(budget, 1);
+        for seed in 0..10 {
+            buffer.frame_completed();
+            buffer.push(state(seed, 100));
+            assert!(buffer.used_memory <= budget);
+        }
+        assert_eq!(buffer.deltas.len(), 2);
+        assert_eq!(buffer.rewind(100), Some((state(7, 100), 2)));
+        assert_eq!(buffer.rewind(1), None);
+    }
+
+    #[test]
+    fn zero_budget_disables_rewinding() {
+        let mut buffer = RewindBuffer::new(0, 1);
+        assert!(!buffer.frame_completed());
+        assert_eq!(buffer.rewind(1), None);
+    }
+}
@@ -0,0 +1,233 @@
+use std::fmt;
+
+pub const STATE_MAGIC: [u8; 4] = *b"CRAB";
+pub const STATE_VERSION: u16 = 6; // Bump whenever the layout of any component state changes
+
+#[derive(Debug)]
+pub enum StateError {
+    InvalidHeader,           // Data is not a save state at all
+    UnsupportedVersion(u16), // State was written by an incompatible version of the core
+    CartridgeMismatch,       // State belongs to a different game
+    UnexpectedEnd,           // State is truncated
+    InvalidData,             // State contents do not fit the running emulator
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StateError::InvalidHeader => write!(formatter, "The data is not a save state"),
+            StateError::UnsupportedVersion(version) => {
+                write!(formatter, "The save state has version {}, this core reads version {}", version, STATE_VERSION)
+            },
+            StateError::CartridgeMismatch => write!(formatter, "The save state belongs to a different game"),
+            StateError::UnexpectedEnd => write!(formatter, "The save state is truncated"),
+            StateError::InvalidData => write!(formatter, "The save state holds data the emulator can't restore"),
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+#[derive(Default)]
+pub struct StateWriter {
+    data: Vec<u8>,
+}
+
+impl StateWriter {
+    pub fn new() -> Self {
+        StateWriter { data: Vec::new() }
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.data.push(value as u8);
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.data.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+pub struct StateReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> StateReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        StateReader { data, position: 0 }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], StateError> {
+        let end = self.position + length;
+        if end > self.data.len() {
+            return Err(StateError::UnexpectedEnd);
+        }
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        Ok(bytes)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, StateError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, StateError> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(StateError::InvalidData),
+        }
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, StateError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, StateError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, StateError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    pub fn read_bytes(&mut self, buffer: &mut [u8]) -> Result<(), StateError> {
+        buffer.copy_from_slice(self.take(buffer.len())?);
+        Ok(())
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.position == self.data.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootrom::Model;
+    use crate::cartridge::CARTRIDGE;
+    use crate::emulator::EMULATOR;
+    use crate::error::CoreError;
+
+    fn emulator(title: &[u8]) -> EMULATOR {
+        let mut rom = vec![0; 0x8000];
+        rom[0x0134..0x0134 + title.len()].copy_from_slice(title);
+        EMULATOR::new(CARTRIDGE::new(rom, None).unwrap(), Model::Dmg, None).unwrap()
+    }
+
+    fn load_error(emulator: &mut EMULATOR, state: &[u8]) -> StateError {
+        match emulator.load_state(state) {
+            Err(CoreError::InvalidState(error)) => error,
+            _ => panic!("The state was not rejected"),
+        }
+    }
+
+    #[test]
+    fn values_round_trip() {
+        let mut writer = StateWriter::new();
+        writer.write_u8(0xAB);
+        writer.write_bool(true);
+        writer.write_u16(0x1234);
+        writer.write_u32(0xDEADBEEF);
+        writer.write_u64(0x0123_4567_89AB_CDEF);
+        writer.write_bytes(&[1, 2, 3]);
+        let data = writer.into_bytes();
+
+        let mut reader = StateReader::new(&data);
+        assert_eq!(reader.read_u8().unwrap(), 0xAB);
+        assert!(reader.read_bool().unwrap());
+        assert_eq!(reader.read_u16().unwrap(), 0x1234);
+        assert_eq!(reader.read_u32().unwrap(), 0xDEADBEEF);
+        assert_eq!(reader.read_u64().unwrap(), 0x0123_4567_89AB_CDEF);
+        let mut bytes = [0; 3];
+        reader.read_bytes(&mut bytes).unwrap();
+        assert_eq!(bytes, [1, 2, 3]);
+        assert!(reader.is_finished());
+        assert!(matches!(reader.read_u8(), Err(StateError::UnexpectedEnd)));
+    }
+
+    #[test]
+    fn invalid_bool_is_rejected() {
+        let mut reader = StateReader::new(&[2]);
+        assert!(matches!(reader.read_bool(), Err(StateError::InvalidData)));
+    }
+
+    #[test]
+    fn emulator_state_round_trip() {
+        let mut emulator = emulator(b"GAME");
+        let state = emulator.save_state();
+
+        let mut registers = emulator.machine_state();
+        registers.a = 0x42;
+        registers.pc = 0x4000;
+        emulator.set_registers(&registers);
+        emulator.write_memory(0xC000, &[0x99]);
+
+        emulator.load_state(&state).unwrap();
+        assert_eq!(emulator.machine_state().a, 0x01);
+        assert_eq!(emulator.machine_state().pc, 0x0100);
+        assert_eq!(emulator.read_memory(0xC000, 1), [0x00]);
+        assert_eq!(emulator.save_state(), state);
+    }
+
+    #[test]
+    fn bad_magic_is_rejected() {
+        let mut emulator = emulator(b"GAME");
+        let mut state = emulator.save_state();
+        state[0] = b'X';
+        assert!(matches!(load_error(&mut emulator, &state), StateError::InvalidHeader));
+        assert!(matches!(load_error(&mut emulator, &[]), StateError::InvalidHeader));
+    }
+
+    #[test]
+    fn wrong_version_is_rejected() {
+        let mut emulator = emulator(b"GAME");
+        let mut state = emulator.save_state();
+        state[4..6].copy_from_slice(&(STATE_VERSION + 1).to_le_bytes());
+        let error = load_error(&mut emulator, &state);
+        assert!(matches!(error, StateError::UnsupportedVersion(version) if version == STATE_VERSION + 1));
+    }
+
+    #[test]
+    fn state_of_another_cartridge_is_rejected() {
+        let state = emulator(b"GAME").save_state();
+        let mut other = emulator(b"OTHER");
+        assert!(matches!(load_error(&mut other, &state), StateError::CartridgeMismatch));
+    }
+
+    #[test]
+    fn truncated_state_leaves_the_emulator_untouched() {
+        let mut emulator = emulator(b"GAME");
+        let state = emulator.save_state();
+        emulator.write_memory(0xC000, &[0x99]);
+        let before = emulator.save_state();
+
+        let error = load_error(&mut emulator, &state[..state.len() - 1]);
+        assert!(matches!(error, StateError::UnexpectedEnd));
+        assert_eq!(emulator.save_state(), before);
+    }
+}
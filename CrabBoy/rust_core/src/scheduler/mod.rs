@@ -0,0 +1,229 @@
+use crate::savestate::{StateError, StateReader, StateWriter};
+
+// Times are counted in clock cycles of the normal speed clock (4194304 Hz), which the PPU and APU run on
+// in both speed modes.
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Event {
+    PpuMode,        // PPU moves to its next mode
+    TimerOverflow,  // TIMA overflows and is reloaded from TMA
+    SerialTransfer, // All 8 bits of a serial transfer have been shifted
+    FrameSequencer, // APU frame sequencer steps
+    DmaTransfer,    // OAM DMA copies its next byte
+}
+
+impl Event {
+    fn id(self) -> u8 {
+        match self {
+            Event::PpuMode => 0,
+            Event::TimerOverflow => 1,
+            Event::SerialTransfer => 2,
+            Event::FrameSequencer => 3,
+            Event::DmaTransfer => 4,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Event::PpuMode),
+            1 => Some(Event::TimerOverflow),
+            2 => Some(Event::SerialTransfer),
+            3 => Some(Event::FrameSequencer),
+            4 => Some(Event::DmaTransfer),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Scheduler {
+    now: u64,                  // Cycles since power on
+    events: Vec<(u64, Event)>, // Pending events and the cycle they fire at, sorted with the next one last
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Scheduler {
+            now: 0,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    // Schedules the event the given number of cycles from now, replacing it if it was already pending.
+    pub fn schedule(&mut self, event: Event, cycles: u64) {
+        self.cancel(event);
+        let time = self.now + cycles;
+        // Events due at the same time fire in the order they were scheduled.
+        let index = self.events.partition_point(|(pending, _)| *pending > time);
+        self.events.insert(index, (time, event));
+    }
+
+    pub fn cancel(&mut self, event: Event) {
+        self.events.retain(|(_, pending)| *pending != event);
+    }
+
+    // Pushes a pending event back, used to freeze components that stop in STOP mode.
+    pub fn postpone(&mut self, event: Event, cycles: u64) {
+        if let Some(index) = self.events.iter().position(|(_, pending)| *pending == event) {
+            let (time, _) = self.events.remove(index);
+            let index = self.events.partition_point(|(pending, _)| *pending > time + cycles);
+            self.events.insert(index, (time + cycles, event));
+        }
+    }
+
+    // Returns the next event due until the given time and moves the clock to it. The clock is left at
+    // the given time once no event is due anymore.
+    pub fn next_event_until(&mut self, time: u64) -> Option<Event> {
+        match self.events.last() {
+            Some((due, _)) if *due <= time => {
+                let (due, event) = self.events.pop().unwrap();
+                self.now = due;
+                Some(event)
+            },
+            _ => {
+                self.now = time;
+                None
+            },
+        }
+    }
+
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        writer.write_u64(self.now);
+        writer.write_u8(self.events.len() as u8);
+        for (time, event) in &self.events {
+            writer.write_u64(*time);
+            writer.write_u8(event.id());
+        }
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.now = reader.read_u64()?;
+        let count = reader.read_u8()?;
+        let mut events = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let time = reader.read_u64()?;
+            let event = Event::from_id(reader.read_u8()?).ok_or(StateError::InvalidData)?;
+            if time < self.now || events.iter().any(|(_, pending)| *pending == event) {
+                return Err(StateError::InvalidData);
+            }
+            events.push((time, event));
+        }
+        if !events.windows(2).all(|pair| pair[0].0 >= pair[1].0) {
+            return Err(StateError::InvalidData);
+        }
+        self.events = events;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Runs the scheduler up to the given time and returns the events that fired together with their times.
+    fn run_until(scheduler: &mut Scheduler, time: u64) -> Vec<(u64, Event)> {
+        let mut fired = Vec::new();
+        while let Some(event) = scheduler.next_event_until(time) {
+            fired.push((scheduler.now(), event));
+        }
+        fired
+    }
+
+    #[test]
+    fn events_fire_in_time_order() {
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(Event::TimerOverflow, 30);
+        scheduler.schedule(Event::PpuMode, 10);
+        scheduler.schedule(Event::DmaTransfer, 20);
+
+        assert_eq!(run_until(&mut scheduler, 25), [(10, Event::PpuMode), (20, Event::DmaTransfer)]);
+        assert_eq!(scheduler.now(), 25);
+        assert_eq!(run_until(&mut scheduler, 100), [(30, Event::TimerOverflow)]);
+        assert_eq!(scheduler.now(), 100);
+    }
+
+    #[test]
+    fn events_due_together_fire_in_scheduling_order() {
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(Event::FrameSequencer, 8);
+        scheduler.schedule(Event::PpuMode, 8);
+        scheduler.schedule(Event::SerialTransfer, 8);
+
+        let fired = run_until(&mut scheduler, 8);
+        assert_eq!(fired, [(8, Event::FrameSequencer), (8, Event::PpuMode), (8, Event::SerialTransfer)]);
+    }
+
+    #[test]
+    fn rescheduling_replaces_the_pending_event() {
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(Event::PpuMode, 10);
+        scheduler.schedule(Event::TimerOverflow, 20);
+        scheduler.schedule(Event::PpuMode, 30);
+
+        assert_eq!(run_until(&mut scheduler, 50), [(20, Event::TimerOverflow), (30, Event::PpuMode)]);
+    }
+
+    #[test]
+    fn events_are_scheduled_relative_to_now() {
+        let mut scheduler = Scheduler::new();
+        run_until(&mut scheduler, 100);
+        scheduler.schedule(Event::PpuMode, 5);
+
+        assert_eq!(run_until(&mut scheduler, 200), [(105, Event::PpuMode)]);
+    }
+
+    #[test]
+    fn postponed_events_keep_their_order() {
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(Event::PpuMode, 10);
+        scheduler.schedule(Event::FrameSequencer, 15);
+        scheduler.postpone(Event::PpuMode, 10);
+        scheduler.postpone(Event::DmaTransfer, 10);
+
+        assert_eq!(run_until(&mut scheduler, 50), [(15, Event::FrameSequencer), (20, Event::PpuMode)]);
+    }
+
+    #[test]
+    fn cancelled_events_never_fire() {
+        let mut scheduler = Scheduler::new();
+        scheduler.schedule(Event::PpuMode, 10);
+        scheduler.schedule(Event::SerialTransfer, 20);
+        scheduler.cancel(Event::PpuMode);
+
+        assert_eq!(run_until(&mut scheduler, 50), [(20, Event::SerialTransfer)]);
+    }
+
+    #[test]
+    fn state_round_trips() {
+        let mut scheduler = Scheduler::new();
+        run_until(&mut scheduler, 40);
+        scheduler.schedule(Event::PpuMode, 10);
+        scheduler.schedule(Event::DmaTransfer, 10);
+        scheduler.schedule(Event::TimerOverflow, 3);
+        let mut writer = StateWriter::new();
+        scheduler.save_state(&mut writer);
+        let state = writer.into_bytes();
+
+        let mut restored = Scheduler::new();
+        restored.load_state(&mut StateReader::new(&state)).unwrap();
+        assert_eq!(restored.now(), 40);
+        assert_eq!(run_until(&mut restored, 100), run_until(&mut scheduler, 100));
+    }
+
+    #[test]
+    fn state_with_unknown_event_is_rejected() {
+        let mut writer = StateWriter::new();
+        writer.write_u64(0);
+        writer.write_u8(1);
+        writer.write_u64(10);
+        writer.write_u8(0xFF);
+        let state = writer.into_bytes();
+
+        let result = Scheduler::new().load_state(&mut StateReader::new(&state));
+        assert!(matches!(result, Err(StateError::InvalidData)));
+    }
+}
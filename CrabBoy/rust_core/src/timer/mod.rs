@@ -1,7 +1,6 @@
-use std::sync::{Arc, Mutex};
-
-use crate::cpu::CPU;
+use crate::savestate::{StateError, StateReader, StateWriter};
 
+#[derive(Clone)]
 pub struct TIMER {
     div: u8,          // DIV (Divider Register)
     tima: u8,          // TIMA (Timer Counter)
@@ -9,7 +8,7 @@ pub struct TIMER {
     tac: u8,           // TAC (Timer Control)
     div_counter: u16,  // Counter for DIV
     tima_counter: u16, // Counter for TIMA
-    cpu: Option<Arc<Mutex<CPU>>>,
+    interrupt_requests: u8, // Interrupts raised since the MMU last collected them
 }
 
 impl TIMER {
@@ -21,12 +20,36 @@ impl TIMER {
             tac: 0,
             div_counter: 0,
             tima_counter: 0,
-            cpu: None,
+            interrupt_requests: 0,
         }
     }
 
-    pub fn set_cpu(&mut self, cpu: Arc<Mutex<CPU>>) {
-        self.cpu = Some(cpu);
+    pub fn take_interrupt_requests(&mut self) -> u8 {
+        std::mem::take(&mut self.interrupt_requests)
+    }
+
+    pub fn set_divider(&mut self, divider: u16) {
+        self.div = (divider >> 8) as u8;
+        self.div_counter = divider & 0xFF;
+    }
+
+    pub fn save_state(&self, writer: &mut StateWriter) {
+        writer.write_u8(self.div);
+        writer.write_u8(self.tima);
+        writer.write_u8(self.tma);
+        writer.write_u8(self.tac);
+        writer.write_u16(self.div_counter);
+        writer.write_u16(self.tima_counter);
+    }
+
+    pub fn load_state(&mut self, reader: &mut StateReader) -> Result<(), StateError> {
+        self.div = reader.read_u8()?;
+        self.tima = reader.read_u8()?;
+        self.tma = reader.read_u8()?;
+        self.tac = reader.read_u8()?;
+        self.div_counter = reader.read_u16()?;
+        self.tima_counter = reader.read_u16()?;
+        Ok(())
     }
 
     pub fn read_byte(&self, address: u16) -> u8 {
@@ -35,7 +58,7 @@ impl TIMER {
             0xFF05 => self.tima,
             0xFF06 => self.tma,
             0xFF07 => self.tac,
-            _ => panic!("Attempted to read from an invalid memory address: {:04X}", address),
+            _ => 0xFF,
         }
     }
 
@@ -45,37 +68,44 @@ impl TIMER {
             0xFF05 => self.tima = value,
             0xFF06 => self.tma = value,
             0xFF07 => self.tac = value,
-            _ => panic!("Attempted to write to an invalid memory address: {:04X}", address),
+            _ => (),
         }
     }
 
-    pub fn tick(&mut self, cycles: u16) {
-        self.div_counter += cycles;
-        if self.div_counter >= 256 {
-            self.div = self.div.wrapping_add(1);
-            self.div_counter -= 256;
+    fn period(&self) -> u64 {
+        match self.tac & 0b11 {
+            0b00 => 1024,  // 4096 Hz (1024 cycles CPU)
+            0b01 => 16,    // 262144 Hz (16 cycles CPU)
+            0b10 => 64,    // 65536 Hz (64 cycles CPU)
+            0b11 => 256,   // 16384 Hz (256 cycles CPU)
+            _ => unreachable!(),
         }
+    }
 
-        if self.tac & 0b100 != 0 {
-            let clock_select = match self.tac & 0b11 {
-                0b00 => 1024,  // 4096 Hz (1024 cycles CPU)
-                0b01 => 16,    // 262144 Hz (16 cycles CPU)
-                0b10 => 64,    // 65536 Hz (64 cycles CPU)
-                0b11 => 256,   // 16384 Hz (256 cycles CPU)
-                _ => unreachable!(),
-            };
+    // CPU cycles until TIMA overflows, None while the timer is stopped.
+    pub fn cycles_until_overflow(&self) -> Option<u64> {
+        if self.tac & 0b100 == 0 {
+            return None;
+        }
+        Some(((256 - self.tima as u64) * self.period()).saturating_sub(self.tima_counter as u64))
+    }
+
+    pub fn advance(&mut self, cycles: u64) {
+        let div_cycles = self.div_counter as u64 + cycles;
+        self.div = self.div.wrapping_add((div_cycles / 256) as u8);
+        self.div_counter = (div_cycles % 256) as u16;
 
-            self.tima_counter += cycles;
+        if self.tac & 0b100 != 0 {
+            let period = self.period();
+            let tima_cycles = self.tima_counter as u64 + cycles;
+            self.tima_counter = (tima_cycles % period) as u16;
 
-            if self.tima_counter >= clock_select {
-                self.tima_counter -= clock_select;
+            for _ in 0..tima_cycles / period {
                 self.tima = self.tima.wrapping_add(1);
 
                 if self.tima == 0 {
                     self.tima = self.tma;
-                    if let Some(ref cpu) = self.cpu {
-                        cpu.lock().unwrap().request_interrupt(0b00000100);
-                    }
+                    self.interrupt_requests |= 0b00000100;
                 }
             }
         }